@@ -1,11 +1,21 @@
+use std::cmp::Reverse;
+
+use algo_toolbox::pqueue::top_k::TopK;
+
 pub struct Solution;
 
 ////////////////////////////////////
 
 impl Solution {
-    pub fn k_closest(mut points: Vec<Vec<i32>>, k: i32) -> Vec<Vec<i32>> {
-        points.sort_by_key(|p| p[0] * p[0] + p[1] * p[1]);
-        points.into_iter().take(k as usize).collect()
+    pub fn k_closest(points: Vec<Vec<i32>>, k: i32) -> Vec<Vec<i32>> {
+        let mut closest: TopK<_> = TopK::new(k as usize);
+        closest.extend(
+            points
+                .into_iter()
+                .map(|p| Reverse((p[0] * p[0] + p[1] * p[1], p))),
+        );
+
+        closest.into_iter().map(|Reverse((_, p))| p).collect()
     }
 }
 
@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-use algo_toolbox::union_find::{SparseUF, UnionFind};
+use algo_toolbox::union_find::{AggregateUF, Sum, UnionFind};
 
 pub struct Solution {}
 
@@ -24,27 +24,24 @@ fn factors(num: usize) -> Vec<usize> {
 
 impl Solution {
     pub fn largest_component_size(nums: Vec<i32>) -> i32 {
-        let mut set = SparseUF::new();
+        let nums: Vec<usize> = nums.iter().map(|&i| i as usize).collect();
+        let capacity = nums.iter().max().map_or(0, |max| max + 1);
 
-        for num in nums.iter().map(|&i| i as usize) {
+        // factors are joined too, but only the numbers from the initial list are counted
+        let nums_set: HashSet<usize> = nums.iter().copied().collect();
+        let mut set = AggregateUF::new(capacity, move |item| {
+            Sum(usize::from(nums_set.contains(&item)))
+        });
+
+        for &num in &nums {
             set.insert(num);
             for factor in factors(num) {
                 set.join(num, factor);
             }
         }
 
-        let nums_set: HashSet<usize> = nums.iter().map(|&n| n as usize).collect();
-
-        set.clusters()
-            .into_iter()
-            .map(|cluster| {
-                // filter out factors leaving only numbers from initial list
-                cluster
-                    .nodes
-                    .into_iter()
-                    .filter(|number| nums_set.contains(&number))
-                    .count()
-            })
+        set.aggregates()
+            .map(|(_, count)| count.0)
             .max()
             .unwrap_or(0) as i32
     }
@@ -1,7 +1,6 @@
-use std::{
-    collections::{BinaryHeap, HashMap},
-    iter::zip,
-};
+use std::{collections::HashMap, iter::zip};
+
+use algo_toolbox::pqueue::{dway_heap::DWayMaxHeap, MaxPriorityQueue};
 
 struct Solution;
 
@@ -10,7 +9,7 @@ impl Solution {
         let mut result = vec![];
 
         let mut freqs = HashMap::with_capacity(nums.len());
-        let mut heap = BinaryHeap::with_capacity(nums.len());
+        let mut heap = DWayMaxHeap::<_, 4>::with_capacity(nums.len());
 
         for (id, freq_change) in zip(nums, freq) {
             let freq_change = freq_change as i64;
@@ -21,14 +20,15 @@ impl Solution {
 
             heap.push((freq, id));
 
-            while let Some(&(freq, id)) = heap.peek() {
+            // outdated frequencies are removed lazily
+            while let Some(&(freq, id)) = heap.max() {
                 if freq != *freqs.get(&id).unwrap() {
-                    heap.pop();
+                    heap.pop_max();
                 } else {
                     break;
                 }
             }
-            if let Some(&(freq, _)) = heap.peek() {
+            if let Some(&(freq, _)) = heap.max() {
                 result.push(freq);
             }
         }
@@ -1,4 +1,4 @@
-use std::{cmp::Reverse, collections::BinaryHeap};
+use algo_toolbox::weighted_graph::DirectedWeightedGraph;
 
 struct Solution;
 
@@ -7,40 +7,25 @@ impl Solution {
         let n = n as usize;
         let k = k as usize - 1;
 
-        let mut adj_list = vec![vec![]; n];
+        let mut graph = DirectedWeightedGraph::<()>::new();
+        for id in 0..n {
+            graph.insert(id, ());
+        }
+
         for (src, dst, weight) in times
             .into_iter()
             .map(|v| (v[0] as usize - 1, v[1] as usize - 1, v[2]))
         {
-            adj_list[src].push((dst, weight));
+            graph.connect(src, dst, weight);
         }
 
-        let mut costs = vec![i32::MAX; n];
-
-        let mut pq = BinaryHeap::with_capacity(n);
-        pq.push(Reverse((0, k)));
+        let paths = graph.shortest_paths_from(k).unwrap();
 
-        while let Some(Reverse((cost, node_idx))) = pq.pop() {
-            for &(neighbor, neighbor_weight) in &adj_list[node_idx] {
-                let new_cost = cost + neighbor_weight;
-                if new_cost < costs[neighbor] {
-                    costs[neighbor] = new_cost;
-                    pq.push(Reverse((new_cost, neighbor)));
-                }
-            }
+        if paths.distances().len() < n {
+            return -1;
         }
 
-        match costs
-            .into_iter()
-            .enumerate()
-            .filter(|&(idx, _)| idx != k)
-            .map(|(_, x)| x)
-            .max()
-        {
-            Some(i32::MAX) => -1,
-            Some(other) => other,
-            None => -1,
-        }
+        paths.distances().values().copied().max().unwrap_or(-1)
     }
 }
 
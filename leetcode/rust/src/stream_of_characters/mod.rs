@@ -1,53 +1,28 @@
-use std::collections::LinkedList;
-
-use algo_toolbox::trie::{
-    hash_trie::{HashTrie, HashTrieNode},
-    StringSet,
-};
+use algo_toolbox::string_search::aho_corasick::AhoCorasick;
 
 /////////////////////////////////////////////////////////////
 
 #[derive(Debug)]
 pub struct StreamChecker {
-    trie: HashTrie<char>,
-    current_matches: LinkedList<*const HashTrieNode<char>>,
+    automaton: AhoCorasick,
 }
 
 impl StreamChecker {
     pub fn new(words: Vec<String>) -> Self {
-        let mut trie = HashTrie::new();
-        for word in words {
-            trie.insert(&word);
-        }
-
         StreamChecker {
-            trie,
-            current_matches: LinkedList::new(),
+            automaton: AhoCorasick::new(words),
         }
     }
 
     pub fn query(&mut self, letter: char) -> bool {
-        let mut result = false;
-        unsafe {
-            for node in std::mem::replace(&mut self.current_matches, LinkedList::new()) {
-                if let Some(child) = node.as_ref().unwrap().find_prefix(std::iter::once(letter)) {
-                    self.current_matches.push_back(child);
-
-                    if child.word_end() {
-                        result = true;
-                    }
-                }
-            }
-
-            if let Some(word_start) = self.trie.root().find_prefix(std::iter::once(letter)) {
-                self.current_matches.push_back(word_start);
-                if word_start.word_end() {
-                    result = true;
-                }
-            }
-        }
-
-        result
+        let mut buffer = [0; 4];
+
+        letter
+            .encode_utf8(&mut buffer)
+            .bytes()
+            .map(|byte| self.automaton.feed(byte).next().is_some())
+            .last()
+            .unwrap_or(false)
     }
 }
 
@@ -92,4 +67,14 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn it_handles_non_ascii_letters() {
+        let mut checker = StreamChecker::new(vec!["dé".to_owned(), "©".to_owned()]);
+
+        assert!(!checker.query('é'));
+        assert!(!checker.query('d'));
+        assert!(checker.query('é'));
+        assert!(checker.query('©'));
+    }
 }
@@ -1,4 +1,4 @@
-use algo_toolbox::union_find::{DenseUF, UnionFind};
+use algo_toolbox::union_find::{AggregateUF, Sum, UnionFind};
 use itertools::Itertools;
 
 pub struct Percolation {
@@ -11,8 +11,10 @@ pub struct Percolation {
         M_0 M_1 ... M_N
           \  ׀     /
         (bottom cell)
+
+        every cluster keeps the number of open cells in it
     */
-    uf: DenseUF,
+    uf: AggregateUF<Sum<usize>>,
     width: usize,
 }
 
@@ -48,8 +50,10 @@ impl Percolation {
         assert_ne!(cols, 0);
 
         // + two hidden cells for the top and bottom
+        let capacity = rows * cols + 2;
+        let is_grid_cell = move |id| id != 0 && id != capacity - 1;
         let mut this = Percolation {
-            uf: DenseUF::new(rows * cols + 2),
+            uf: AggregateUF::new(capacity, move |id| Sum(usize::from(is_grid_cell(id)))),
             width: cols,
         };
 
@@ -102,6 +106,13 @@ impl Percolation {
         self.uf.connected(self.top_id(), self.dsu_id_for(row, col))
     }
 
+    /// number of open cells connected to the given one (including itself).
+    /// All open cells of the top (bottom) row are connected through the hidden top (bottom) cell
+    pub fn open_cluster_size(&mut self, row: usize, col: usize) -> usize {
+        let id = self.dsu_id_for(row, col);
+        self.uf.aggregate_of(id).map_or(0, |sum| sum.0)
+    }
+
     pub fn count_open(&self) -> usize {
         self.uf.len() - 2
     }
@@ -168,6 +179,23 @@ mod tests {
         assert!(p.percolates());
     }
 
+    #[test]
+    fn open_cluster_size_ignores_hidden_cells() {
+        let mut p = Percolation::new(3, 3);
+        assert_eq!(p.open_cluster_size(0, 0), 0);
+
+        p.open(0, 0);
+        p.open(1, 0);
+        p.open(2, 0);
+        p.open(0, 2);
+        p.open(1, 2);
+        assert_eq!(p.open_cluster_size(1, 0), 5);
+
+        p.open(2, 2);
+        assert_eq!(p.open_cluster_size(2, 2), 6);
+        assert_eq!(p.open_cluster_size(1, 1), 0);
+    }
+
     #[test]
     fn open_works() {
         let mut p = Percolation::new(100, 100);
@@ -0,0 +1,70 @@
+use std::time::Duration;
+
+use criterion::{criterion_group, BenchmarkId, Criterion, Throughput};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use algo_toolbox::weighted_graph::WeightedGraph;
+
+fn generate_random_graph(n_nodes: usize, n_edges: usize) -> WeightedGraph<(), u32> {
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut graph = WeightedGraph::new();
+
+    for id in 0..n_nodes {
+        graph.insert(id, ());
+    }
+
+    // a random spanning tree keeps the graph connected
+    for id in 1..n_nodes {
+        graph.connect(rng.gen_range(0..id), id, rng.gen_range(0..1000));
+    }
+
+    for _ in n_nodes..n_edges {
+        let id1 = rng.gen_range(0..n_nodes);
+        let id2 = rng.gen_range(0..n_nodes);
+        if id1 != id2 {
+            graph.connect(id1, id2, rng.gen_range(0..1000));
+        }
+    }
+
+    graph
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("graph layouts");
+    for n_nodes in (3..=5).map(|p| usize::pow(10, p)) {
+        let n_edges = n_nodes * 4;
+
+        group
+            .throughput(Throughput::Elements(n_edges as u64))
+            .sampling_mode(criterion::SamplingMode::Flat)
+            .sample_size(10)
+            .warm_up_time(Duration::from_secs(1));
+
+        let graph = generate_random_graph(n_nodes, n_edges);
+        let frozen = generate_random_graph(n_nodes, n_edges).freeze();
+        let size = format!("{}-{}", n_nodes, n_edges);
+
+        group.bench_function(BenchmarkId::new("kruskal hashmap", &size), |b| {
+            b.iter(|| graph.mst_kruskal())
+        });
+        group.bench_function(BenchmarkId::new("kruskal csr", &size), |b| {
+            b.iter(|| frozen.mst_kruskal())
+        });
+        group.bench_function(BenchmarkId::new("prim hashmap", &size), |b| {
+            b.iter(|| graph.mst_prim())
+        });
+        group.bench_function(BenchmarkId::new("prim csr", &size), |b| {
+            b.iter(|| frozen.mst_prim())
+        });
+        group.bench_function(BenchmarkId::new("bfs hashmap", &size), |b| {
+            b.iter(|| graph.bfs(0).count())
+        });
+        group.bench_function(BenchmarkId::new("bfs csr", &size), |b| {
+            b.iter(|| frozen.bfs(0).count())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(bench, criterion_benchmark);
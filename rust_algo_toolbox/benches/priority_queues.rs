@@ -1,4 +1,10 @@
-use algo_toolbox::pqueue::dway_heap::DWayMaxHeap;
+use algo_toolbox::pqueue::{
+    dway_heap::{DWayMaxHeap, DWayMinHeap},
+    leftist_heap::LeftistMaxHeap,
+    min_max_heap::MinMaxHeap,
+    pairing_heap::PairingMaxHeap,
+    MaxPriorityQueue,
+};
 use std::{collections::BinaryHeap, time::Duration};
 
 use criterion::{criterion_group, BenchmarkId, Criterion};
@@ -29,6 +35,71 @@ fn pqueue_bench(c: &mut Criterion) {
     for (dataset_name, data_gen) in datasets {
         bench_on_dataset(c, dataset_name, data_gen);
     }
+
+    meld_bench(c);
+}
+
+/// merge many small heaps into one, like in a k-way merge
+fn meld_bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("heap meld");
+
+    group
+        .sampling_mode(criterion::SamplingMode::Flat)
+        .sample_size(20)
+        .measurement_time(Duration::from_secs(2))
+        .warm_up_time(Duration::from_millis(500));
+
+    let heap_size = 100;
+    for heaps_count in [1_000, 3_000, 10_000] {
+        let data = random_array(heaps_count * heap_size);
+
+        group.bench_with_input(
+            BenchmarkId::new("4-way heap", heaps_count),
+            &data,
+            |b, data| {
+                b.iter(|| {
+                    let mut merged = DWayMaxHeap::<i32, 4>::new();
+                    for chunk in data.chunks(heap_size) {
+                        let mut heap = DWayMaxHeap::<i32, 4>::from_slice(chunk);
+                        while let Some(val) = heap.pop_max() {
+                            merged.push(val);
+                        }
+                    }
+                    merged
+                });
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("pairing heap", heaps_count),
+            &data,
+            |b, data| {
+                b.iter(|| {
+                    let mut merged = PairingMaxHeap::new();
+                    for chunk in data.chunks(heap_size) {
+                        merged.meld(chunk.iter().cloned().collect());
+                    }
+                    merged
+                });
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("leftist heap", heaps_count),
+            &data,
+            |b, data| {
+                b.iter(|| {
+                    let mut merged = LeftistMaxHeap::new();
+                    for chunk in data.chunks(heap_size) {
+                        merged.meld(chunk.iter().cloned().collect());
+                    }
+                    merged
+                });
+            },
+        );
+    }
+
+    group.finish();
 }
 
 fn bench_on_dataset(
@@ -68,6 +139,38 @@ fn bench_on_dataset(
                 let _: Vec<i32> = heap.clone().into_sorted_vec();
             });
         });
+
+        group.bench_with_input(BenchmarkId::new("4-way min heap", i), &i, |b, i| {
+            let data = data_gen(*i);
+            b.iter(|| {
+                let heap: DWayMinHeap<i32, 4> = data.iter().cloned().collect();
+                let _: Vec<i32> = heap.clone().into_sorted_vec();
+            });
+        });
+
+        group.bench_with_input(BenchmarkId::new("min-max heap", i), &i, |b, i| {
+            let data = data_gen(*i);
+            b.iter(|| {
+                let heap: MinMaxHeap<i32> = data.iter().cloned().collect();
+                let _: Vec<i32> = heap.clone().into_sorted_vec();
+            });
+        });
+
+        group.bench_with_input(BenchmarkId::new("pairing heap", i), &i, |b, i| {
+            let data = data_gen(*i);
+            b.iter(|| {
+                let heap: PairingMaxHeap<i32> = data.iter().cloned().collect();
+                let _: Vec<i32> = heap.into_sorted_vec();
+            });
+        });
+
+        group.bench_with_input(BenchmarkId::new("leftist heap", i), &i, |b, i| {
+            let data = data_gen(*i);
+            b.iter(|| {
+                let heap: LeftistMaxHeap<i32> = data.iter().cloned().collect();
+                let _: Vec<i32> = heap.into_sorted_vec();
+            });
+        });
     }
 
     group.finish();
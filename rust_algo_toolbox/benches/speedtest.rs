@@ -3,5 +3,13 @@ use criterion::criterion_main;
 mod union_find;
 mod radix_sorts;
 mod priority_queues;
+mod graphs;
+mod string_search;
 
-criterion_main!(union_find::bench, radix_sorts::bench, priority_queues::bench);
+criterion_main!(
+    union_find::bench,
+    radix_sorts::bench,
+    priority_queues::bench,
+    graphs::bench,
+    string_search::bench
+);
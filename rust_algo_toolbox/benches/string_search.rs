@@ -0,0 +1,44 @@
+use std::time::Duration;
+
+use algo_toolbox::string_search::{
+    boyer_moore::BoyerMoore, knuth_morris_pratt::KnuthMorrisPratt, rabin_karp::RabinKarp,
+    two_way::TwoWay, z_algorithm::ZAlgorithm, Searcher,
+};
+use criterion::{criterion_group, BenchmarkGroup, BenchmarkId, Criterion, Throughput};
+
+fn bench_searcher<S: Searcher>(
+    group: &mut BenchmarkGroup<'_, criterion::measurement::WallTime>,
+    name: &str,
+    searcher: S,
+    text: &[u8],
+) {
+    let pattern = String::from_utf8_lossy(searcher.pattern()).into_owned();
+
+    group.bench_with_input(BenchmarkId::new(name, pattern), text, |b, text| {
+        b.iter(|| searcher.find_iter(text).count())
+    });
+}
+
+fn string_search_bench(c: &mut Criterion) {
+    let text = include_str!("words_list.txt").as_bytes();
+
+    let mut group = c.benchmark_group("string search wordlist");
+    group
+        .throughput(Throughput::Bytes(text.len() as u64))
+        .sampling_mode(criterion::SamplingMode::Flat)
+        .sample_size(10)
+        .measurement_time(Duration::from_secs(5))
+        .warm_up_time(Duration::from_secs(1));
+
+    for pattern in ["the", "ing", "interdenominational", "zzzzzzzz"] {
+        bench_searcher(&mut group, "boyer-moore", BoyerMoore::new(pattern), text);
+        bench_searcher(&mut group, "rabin-karp", RabinKarp::new(pattern), text);
+        bench_searcher(&mut group, "kmp", KnuthMorrisPratt::new(pattern), text);
+        bench_searcher(&mut group, "z-algorithm", ZAlgorithm::new(pattern), text);
+        bench_searcher(&mut group, "two-way", TwoWay::new(pattern), text);
+    }
+
+    group.finish();
+}
+
+criterion_group!(bench, string_search_bench);
@@ -5,7 +5,8 @@ use itertools::Itertools;
 use rand::seq::SliceRandom;
 use rand::Rng;
 
-use algo_toolbox::union_find::{DenseUF, UnionFind};
+use algo_toolbox::union_find::{ConcurrentUF, DenseUF, UnionFind};
+use rayon::prelude::*;
 
 fn generate_random_edges(n_nodes: usize, n_edges: usize) -> Vec<(usize, usize)> {
     let mut rng = rand::thread_rng();
@@ -53,4 +54,47 @@ fn criterion_benchmark(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(bench, criterion_benchmark);
+fn bench_parallel_union(uf: ConcurrentUF, pairs: &Vec<(usize, usize)>) {
+    pairs.par_iter().for_each(|&(id1, id2)| {
+        uf.join(id1, id2);
+    });
+}
+
+fn parallel_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parallel union");
+    for n_nodes in (4..=6).map(|p| usize::pow(10, p)) {
+        let n_edges = n_nodes * 4;
+
+        group
+            .throughput(Throughput::Elements(n_edges as u64))
+            .sampling_mode(criterion::SamplingMode::Flat)
+            .sample_size(10)
+            .warm_up_time(Duration::from_secs(1));
+
+        group.bench_with_input(
+            format!("dense {}-{}", n_nodes, n_edges),
+            &(n_nodes, n_edges),
+            |b, &(n_nodes, n_edges)| {
+                let pairs = &generate_random_edges(n_nodes, n_edges);
+                let uf = DenseUF::new(n_nodes);
+                b.iter(|| bench_union(uf.clone(), pairs));
+            },
+        );
+
+        group.bench_with_input(
+            format!("concurrent {}-{}", n_nodes, n_edges),
+            &(n_nodes, n_edges),
+            |b, &(n_nodes, n_edges)| {
+                let pairs = &generate_random_edges(n_nodes, n_edges);
+                b.iter_batched(
+                    || ConcurrentUF::new(n_nodes),
+                    |uf| bench_parallel_union(uf, pairs),
+                    criterion::BatchSize::LargeInput,
+                );
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(bench, criterion_benchmark, parallel_benchmark);
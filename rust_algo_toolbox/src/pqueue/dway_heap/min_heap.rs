@@ -0,0 +1,175 @@
+//! D-way min-heap implementation
+
+use std::cmp::Reverse;
+
+use crate::pqueue::{MaxPriorityQueue, MinPriorityQueue};
+
+use super::DWayMaxHeap;
+
+/// D-way min-heap.
+/// It's a [`DWayMaxHeap`] of reversed values
+#[derive(Debug, Clone)]
+pub struct DWayMinHeap<T, const D: usize = 4> {
+    heap: DWayMaxHeap<Reverse<T>, D>,
+}
+
+impl<T: Ord, const D: usize> DWayMinHeap<T, D> {
+    pub fn new() -> Self {
+        Self {
+            heap: DWayMaxHeap::new(),
+        }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            heap: DWayMaxHeap::with_capacity(capacity),
+        }
+    }
+
+    pub fn from_slice(data: &[T]) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_iter(data.iter().cloned())
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// values in ascending order
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        self.into_iter().collect()
+    }
+}
+
+impl<T: Ord, const D: usize> Default for DWayMinHeap<T, D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord, const D: usize> FromIterator<T> for DWayMinHeap<T, D> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self {
+            heap: iter.into_iter().map(Reverse).collect(),
+        }
+    }
+}
+
+impl<T: Ord, const D: usize> MinPriorityQueue<T> for DWayMinHeap<T, D> {
+    fn min(&self) -> Option<&T> {
+        self.heap.max().map(|Reverse(val)| val)
+    }
+
+    fn pop_min(&mut self) -> Option<T> {
+        self.heap.pop_max().map(|Reverse(val)| val)
+    }
+
+    fn push(&mut self, val: T) {
+        self.heap.push(Reverse(val));
+    }
+}
+
+pub struct DWayMinHeapIter<T, const D: usize> {
+    heap: DWayMinHeap<T, D>,
+}
+
+impl<T: Ord, const D: usize> Iterator for DWayMinHeapIter<T, D> {
+    type Item = T;
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.heap.len(), Some(self.heap.len()))
+    }
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.heap.pop_min()
+    }
+}
+
+impl<T: Ord, const D: usize> IntoIterator for DWayMinHeap<T, D> {
+    type Item = T;
+    type IntoIter = DWayMinHeapIter<T, D>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        DWayMinHeapIter { heap: self }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use rand::{Rng, SeedableRng};
+    use tap::Tap;
+
+    use super::*;
+
+    const D: usize = 4;
+
+    #[rstest::fixture]
+    fn rng() -> impl Rng {
+        rand::rngs::StdRng::from_seed([0; 32])
+    }
+
+    #[rstest::fixture]
+    fn data(rng: impl Rng) -> Vec<i32> {
+        let distr = rand::distributions::Uniform::new_inclusive(-1000, 1000);
+        rng.sample_iter(distr).take(1000).collect()
+    }
+
+    #[rstest::rstest]
+    fn min_returns_smallest_val(data: Vec<i32>) {
+        let heap: DWayMinHeap<i32, D> = data.iter().cloned().collect();
+        assert!(data.iter().all(|x| x >= heap.min().unwrap()));
+    }
+
+    #[rstest::rstest]
+    fn pop_min_removes_smallest_val(data: Vec<i32>) {
+        let min_val = *data.iter().min().unwrap();
+
+        let mut heap: DWayMinHeap<i32, D> = data.iter().cloned().collect();
+        assert_eq!(heap.pop_min(), Some(min_val));
+        assert_eq!(heap.len(), data.len() - 1);
+    }
+
+    #[rstest::rstest]
+    fn pop_min_returns_none_on_empty_heap() {
+        let mut heap: DWayMinHeap<i32, D> = DWayMinHeap::new();
+        assert_eq!(heap.pop_min(), None);
+    }
+
+    #[rstest::rstest]
+    fn heap_is_sorted(data: Vec<i32>) {
+        let heap: DWayMinHeap<i32, D> = data.iter().cloned().collect();
+        let heap_sorted = heap.into_iter().collect::<Vec<_>>();
+        let sorted = data.tap_mut(|vec| vec.sort_unstable());
+
+        assert_eq!(heap_sorted, sorted);
+    }
+
+    proptest! {
+        #[test]
+        fn interleaved_pushes_and_pops_match_sorted_model(
+            ops in prop::collection::vec(prop::option::of(-100i32..100), 0..200)
+        ) {
+            let mut heap = DWayMinHeap::<i32, 3>::new();
+            let mut model = vec![];
+
+            for op in ops {
+                match op {
+                    Some(val) => {
+                        heap.push(val);
+                        model.push(val);
+                        model.sort_unstable_by(|a, b| b.cmp(a));
+                    }
+                    None => prop_assert_eq!(heap.pop_min(), model.pop()),
+                }
+                prop_assert_eq!(heap.min(), model.last());
+            }
+        }
+    }
+}
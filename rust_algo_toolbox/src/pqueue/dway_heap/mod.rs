@@ -1,6 +1,9 @@
-//! D-way max-heap implementation
+//! D-way max-heap and min-heap implementations
 
 pub mod iterator;
+mod min_heap;
+
+pub use min_heap::{DWayMinHeap, DWayMinHeapIter};
 
 use super::MaxPriorityQueue;
 
@@ -0,0 +1,364 @@
+//! Addressable d-way heap
+//!
+//! Every pushed value gets a [`Handle`] which can be used later to read, re-prioritize or remove it.
+//! The same heap is used as a min-heap or as a max-heap depending on its [`HeapOrder`]
+
+use std::marker::PhantomData;
+
+use super::{MaxPriorityQueue, MinPriorityQueue};
+
+/// Reference to a value pushed into an [`IndexedHeap`].
+/// Handles are never reused, so a handle of a removed value stays invalid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Handle(usize);
+
+/// Order of values in the heap
+pub trait HeapOrder {
+    /// check if `a` should be closer to the top of the heap than `b`
+    fn before<T: Ord>(a: &T, b: &T) -> bool;
+}
+
+/// smallest value on top
+#[derive(Debug, Clone, Copy)]
+pub struct MinOrder;
+
+impl HeapOrder for MinOrder {
+    fn before<T: Ord>(a: &T, b: &T) -> bool {
+        a < b
+    }
+}
+
+/// biggest value on top
+#[derive(Debug, Clone, Copy)]
+pub struct MaxOrder;
+
+impl HeapOrder for MaxOrder {
+    fn before<T: Ord>(a: &T, b: &T) -> bool {
+        a > b
+    }
+}
+
+/// D-way heap supporting priority changes and removal of arbitrary values
+#[derive(Debug, Clone)]
+pub struct IndexedHeap<T, O, const D: usize = 4> {
+    /// values with their handles in heap order
+    data: Vec<(T, Handle)>,
+    /// position of every handle's value in `data`
+    positions: Vec<Option<usize>>,
+    order: PhantomData<O>,
+}
+
+/// Addressable d-way min-heap
+pub type IndexedMinHeap<T, const D: usize = 4> = IndexedHeap<T, MinOrder, D>;
+
+/// Addressable d-way max-heap
+pub type IndexedMaxHeap<T, const D: usize = 4> = IndexedHeap<T, MaxOrder, D>;
+
+impl<T: Ord, O: HeapOrder, const D: usize> Default for IndexedHeap<T, O, D> {
+    fn default() -> Self {
+        Self {
+            data: vec![],
+            positions: vec![],
+            order: PhantomData,
+        }
+    }
+}
+
+impl<T: Ord, O: HeapOrder, const D: usize> IndexedHeap<T, O, D> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            data: Vec::with_capacity(capacity),
+            positions: Vec::with_capacity(capacity),
+            order: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// push a value and return a handle to it
+    pub fn push(&mut self, val: T) -> Handle {
+        let handle = Handle(self.positions.len());
+        let idx = self.data.len();
+
+        self.positions.push(Some(idx));
+        self.data.push((val, handle));
+        self._bubble_up(idx);
+
+        handle
+    }
+
+    /// value on top of the heap with its handle
+    pub fn peek(&self) -> Option<(Handle, &T)> {
+        self.data.first().map(|(val, handle)| (*handle, val))
+    }
+
+    /// remove the value on top of the heap and return it with its handle
+    pub fn pop(&mut self) -> Option<(Handle, T)> {
+        let handle = self.data.first()?.1;
+
+        self.remove(handle).map(|val| (handle, val))
+    }
+
+    /// check if the value of the handle is still in the heap
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.position_of(handle).is_some()
+    }
+
+    /// value of the handle if it's still in the heap
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        self.position_of(handle).map(|idx| &self.data[idx].0)
+    }
+
+    /// replace the value of the handle and restore the heap order.
+    /// Returns the previous value, or `None` if the handle's value isn't in the heap
+    pub fn change_priority(&mut self, handle: Handle, val: T) -> Option<T> {
+        let idx = self.position_of(handle)?;
+        let previous = std::mem::replace(&mut self.data[idx].0, val);
+
+        self._bubble_up(idx);
+        self._bubble_down(self.position_of(handle).unwrap());
+
+        Some(previous)
+    }
+
+    /// remove the value of the handle from the heap
+    pub fn remove(&mut self, handle: Handle) -> Option<T> {
+        let idx = self.position_of(handle)?;
+        let last = self.data.len() - 1;
+
+        self._swap(idx, last);
+        let (val, _) = self.data.pop().unwrap();
+        self.positions[handle.0] = None;
+
+        if idx < self.data.len() {
+            let moved = self.data[idx].1;
+            self._bubble_up(idx);
+            self._bubble_down(self.position_of(moved).unwrap());
+        }
+
+        Some(val)
+    }
+
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut result = Vec::with_capacity(self.data.len());
+
+        while let Some((_, val)) = self.pop() {
+            result.push(val);
+        }
+
+        result
+    }
+
+    fn position_of(&self, handle: Handle) -> Option<usize> {
+        self.positions.get(handle.0).copied().flatten()
+    }
+
+    fn _swap(&mut self, idx1: usize, idx2: usize) {
+        self.data.swap(idx1, idx2);
+        self.positions[self.data[idx1].1 .0] = Some(idx1);
+        self.positions[self.data[idx2].1 .0] = Some(idx2);
+    }
+
+    fn _bubble_up(&mut self, mut idx: usize) {
+        while idx > 0 {
+            let parent_idx = (idx - 1) / D;
+
+            if !O::before(&self.data[idx].0, &self.data[parent_idx].0) {
+                return;
+            }
+
+            self._swap(idx, parent_idx);
+            idx = parent_idx;
+        }
+    }
+
+    fn _bubble_down(&mut self, mut idx: usize) {
+        loop {
+            let slice_start = idx * D + 1;
+            let slice_end = (slice_start + D).min(self.data.len());
+
+            let mut top_idx = idx;
+            for i in slice_start..slice_end {
+                if O::before(&self.data[i].0, &self.data[top_idx].0) {
+                    top_idx = i;
+                }
+            }
+
+            if idx == top_idx {
+                return;
+            }
+
+            self._swap(idx, top_idx);
+            idx = top_idx;
+        }
+    }
+}
+
+impl<T: Ord, O: HeapOrder, const D: usize> FromIterator<T> for IndexedHeap<T, O, D> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut heap = Self::new();
+        for val in iter {
+            heap.push(val);
+        }
+        heap
+    }
+}
+
+impl<T: Ord, const D: usize> MinPriorityQueue<T> for IndexedHeap<T, MinOrder, D> {
+    fn min(&self) -> Option<&T> {
+        self.peek().map(|(_, val)| val)
+    }
+
+    fn pop_min(&mut self) -> Option<T> {
+        self.pop().map(|(_, val)| val)
+    }
+
+    fn push(&mut self, val: T) {
+        IndexedHeap::push(self, val);
+    }
+}
+
+impl<T: Ord, const D: usize> MaxPriorityQueue<T> for IndexedHeap<T, MaxOrder, D> {
+    fn max(&self) -> Option<&T> {
+        self.peek().map(|(_, val)| val)
+    }
+
+    fn pop_max(&mut self) -> Option<T> {
+        self.pop().map(|(_, val)| val)
+    }
+
+    fn push(&mut self, val: T) {
+        IndexedHeap::push(self, val);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use rand::{Rng, SeedableRng};
+    use tap::Tap;
+
+    use super::*;
+
+    #[rstest::fixture]
+    fn rng() -> impl Rng {
+        rand::rngs::StdRng::from_seed([0; 32])
+    }
+
+    #[rstest::fixture]
+    fn data(rng: impl Rng) -> Vec<i32> {
+        let distr = rand::distributions::Uniform::new_inclusive(-1000, 1000);
+        rng.sample_iter(distr).take(1000).collect()
+    }
+
+    #[rstest::rstest]
+    fn min_heap_is_sorted(data: Vec<i32>) {
+        let heap: IndexedMinHeap<i32> = data.iter().cloned().collect();
+        assert_eq!(heap.min(), data.iter().min());
+
+        let sorted = data.tap_mut(|vec| vec.sort_unstable());
+        assert_eq!(heap.into_sorted_vec(), sorted);
+    }
+
+    #[rstest::rstest]
+    fn max_heap_is_sorted(data: Vec<i32>) {
+        let heap: IndexedMaxHeap<i32, 8> = data.iter().cloned().collect();
+        assert_eq!(heap.max(), data.iter().max());
+
+        let sorted = data.tap_mut(|vec| vec.sort_unstable_by_key(|val| -val));
+        assert_eq!(heap.into_sorted_vec(), sorted);
+    }
+
+    #[test]
+    fn handles_follow_their_values() {
+        let mut heap = IndexedMinHeap::<_, 2>::new();
+        let a = heap.push(5);
+        let b = heap.push(3);
+        let c = heap.push(8);
+
+        assert_eq!(heap.peek(), Some((b, &3)));
+        assert_eq!(heap.change_priority(c, 1), Some(8));
+        assert_eq!(heap.peek(), Some((c, &1)));
+        assert_eq!(heap.get(a), Some(&5));
+
+        assert_eq!(heap.remove(c), Some(1));
+        assert!(!heap.contains(c));
+        assert_eq!(heap.remove(c), None);
+        assert_eq!(heap.change_priority(c, 0), None);
+
+        assert_eq!(heap.pop(), Some((b, 3)));
+        assert_eq!(heap.pop(), Some((a, 5)));
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[derive(Debug, Clone)]
+    enum Op {
+        Push(i32),
+        Pop,
+        Change(usize, i32),
+        Remove(usize),
+    }
+
+    fn op() -> impl Strategy<Value = Op> {
+        prop_oneof![
+            (-100i32..100).prop_map(Op::Push),
+            Just(Op::Pop),
+            (0usize..50, -100i32..100).prop_map(|(idx, val)| Op::Change(idx, val)),
+            (0usize..50).prop_map(Op::Remove),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn heap_matches_model(ops in prop::collection::vec(op(), 0..200)) {
+            let mut heap = IndexedHeap::<i32, MinOrder, 3>::new();
+            // values of handles still in the heap
+            let mut model: Vec<(Handle, i32)> = vec![];
+            let mut handles = vec![];
+
+            for op in ops {
+                match op {
+                    Op::Push(val) => {
+                        let handle = heap.push(val);
+                        handles.push(handle);
+                        model.push((handle, val));
+                    }
+                    Op::Pop => {
+                        let expected = model.iter().map(|(_, val)| *val).min();
+                        let popped = heap.pop();
+                        prop_assert_eq!(popped.map(|(_, val)| val), expected);
+                        if let Some((handle, _)) = popped {
+                            model.retain(|(h, _)| *h != handle);
+                        }
+                    }
+                    Op::Change(idx, val) if idx < handles.len() => {
+                        let handle = handles[idx];
+                        let entry = model.iter_mut().find(|(h, _)| *h == handle);
+                        let expected = entry.map(|entry| std::mem::replace(&mut entry.1, val));
+                        prop_assert_eq!(heap.change_priority(handle, val), expected);
+                    }
+                    Op::Remove(idx) if idx < handles.len() => {
+                        let handle = handles[idx];
+                        let expected = model.iter().find(|(h, _)| *h == handle).map(|(_, val)| *val);
+                        model.retain(|(h, _)| *h != handle);
+                        prop_assert_eq!(heap.remove(handle), expected);
+                    }
+                    _ => {}
+                }
+
+                prop_assert_eq!(heap.len(), model.len());
+                prop_assert_eq!(heap.min().copied(), model.iter().map(|(_, val)| *val).min());
+            }
+        }
+    }
+}
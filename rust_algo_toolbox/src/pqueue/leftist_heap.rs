@@ -0,0 +1,259 @@
+//! Leftist heap
+//!
+//! Heap-ordered binary tree whose right spine is at most logarithmic,
+//! so `meld`, `push` and removal of the top value are O(log n) in the worst case.
+//! The same heap is used as a min-heap or as a max-heap depending on its [`HeapOrder`]
+
+use std::{fmt::Debug, marker::PhantomData};
+
+use super::{
+    indexed_heap::{HeapOrder, MaxOrder, MinOrder},
+    MaxPriorityQueue, MinPriorityQueue,
+};
+
+type Tree<T> = Option<Box<Node<T>>>;
+
+struct Node<T> {
+    val: T,
+    /// length of the right spine
+    rank: usize,
+    left: Tree<T>,
+    right: Tree<T>,
+}
+
+/// Mergeable heap with O(log n) meld
+pub struct LeftistHeap<T, O> {
+    root: Tree<T>,
+    len: usize,
+    order: PhantomData<O>,
+}
+
+/// Leftist min-heap
+pub type LeftistMinHeap<T> = LeftistHeap<T, MinOrder>;
+
+/// Leftist max-heap
+pub type LeftistMaxHeap<T> = LeftistHeap<T, MaxOrder>;
+
+impl<T: Ord, O: HeapOrder> Default for LeftistHeap<T, O> {
+    fn default() -> Self {
+        Self {
+            root: None,
+            len: 0,
+            order: PhantomData,
+        }
+    }
+}
+
+impl<T: Ord, O: HeapOrder> LeftistHeap<T, O> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn push(&mut self, val: T) {
+        let node = Box::new(Node {
+            val,
+            rank: 1,
+            left: None,
+            right: None,
+        });
+
+        self.root = Self::_merge(self.root.take(), Some(node));
+        self.len += 1;
+    }
+
+    /// value on top of the heap
+    pub fn peek(&self) -> Option<&T> {
+        self.root.as_ref().map(|root| &root.val)
+    }
+
+    /// remove the value on top of the heap
+    pub fn pop(&mut self) -> Option<T> {
+        let mut root = self.root.take()?;
+        self.len -= 1;
+
+        self.root = Self::_merge(root.left.take(), root.right.take());
+
+        Some(root.val)
+    }
+
+    /// move all values of the other heap into this one in O(log n)
+    pub fn meld(&mut self, mut other: Self) {
+        self.len += std::mem::take(&mut other.len);
+        self.root = Self::_merge(self.root.take(), other.root.take());
+    }
+
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut result = Vec::with_capacity(self.len);
+
+        while let Some(val) = self.pop() {
+            result.push(val);
+        }
+
+        result
+    }
+
+    fn _rank(tree: &Tree<T>) -> usize {
+        tree.as_ref().map_or(0, |node| node.rank)
+    }
+
+    /// merge right spines of the trees.
+    /// Recursion depth is bounded by the sum of the ranks, which are logarithmic
+    fn _merge(a: Tree<T>, b: Tree<T>) -> Tree<T> {
+        let (mut a, mut b) = match (a, b) {
+            (Some(a), Some(b)) => (a, b),
+            (a, b) => return a.or(b),
+        };
+
+        if O::before(&b.val, &a.val) {
+            std::mem::swap(&mut a, &mut b);
+        }
+
+        a.right = Self::_merge(a.right.take(), Some(b));
+        if Self::_rank(&a.left) < Self::_rank(&a.right) {
+            std::mem::swap(&mut a.left, &mut a.right);
+        }
+        a.rank = Self::_rank(&a.right) + 1;
+
+        Some(a)
+    }
+}
+
+impl<T, O> Drop for LeftistHeap<T, O> {
+    // left spine isn't bounded, so trees are dismantled without recursion
+    fn drop(&mut self) {
+        let mut stack: Vec<_> = self.root.take().into_iter().collect();
+
+        while let Some(mut node) = stack.pop() {
+            stack.extend(node.left.take());
+            stack.extend(node.right.take());
+        }
+    }
+}
+
+impl<T: Ord + Debug, O: HeapOrder> Debug for LeftistHeap<T, O> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LeftistHeap")
+            .field("top", &self.peek())
+            .field("len", &self.len)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: Ord, O: HeapOrder> FromIterator<T> for LeftistHeap<T, O> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut heap = Self::new();
+        for val in iter {
+            heap.push(val);
+        }
+        heap
+    }
+}
+
+impl<T: Ord> MinPriorityQueue<T> for LeftistHeap<T, MinOrder> {
+    fn min(&self) -> Option<&T> {
+        self.peek()
+    }
+
+    fn pop_min(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    fn push(&mut self, val: T) {
+        LeftistHeap::push(self, val)
+    }
+}
+
+impl<T: Ord> MaxPriorityQueue<T> for LeftistHeap<T, MaxOrder> {
+    fn max(&self) -> Option<&T> {
+        self.peek()
+    }
+
+    fn pop_max(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    fn push(&mut self, val: T) {
+        LeftistHeap::push(self, val)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use rand::{Rng, SeedableRng};
+    use tap::Tap;
+
+    use super::*;
+
+    #[rstest::fixture]
+    fn rng() -> impl Rng {
+        rand::rngs::StdRng::from_seed([0; 32])
+    }
+
+    #[rstest::fixture]
+    fn data(rng: impl Rng) -> Vec<i32> {
+        let distr = rand::distributions::Uniform::new_inclusive(-1000, 1000);
+        rng.sample_iter(distr).take(1000).collect()
+    }
+
+    #[rstest::rstest]
+    fn min_heap_is_sorted(data: Vec<i32>) {
+        let heap: LeftistMinHeap<i32> = data.iter().cloned().collect();
+        assert_eq!(heap.min(), data.iter().min());
+        assert_eq!(heap.len(), data.len());
+
+        let sorted = data.tap_mut(|vec| vec.sort_unstable());
+        assert_eq!(heap.into_sorted_vec(), sorted);
+    }
+
+    #[rstest::rstest]
+    fn max_heap_is_sorted(data: Vec<i32>) {
+        let heap: LeftistMaxHeap<i32> = data.iter().cloned().collect();
+        assert_eq!(heap.max(), data.iter().max());
+
+        let sorted = data.tap_mut(|vec| vec.sort_unstable_by_key(|val| -val));
+        assert_eq!(heap.into_sorted_vec(), sorted);
+    }
+
+    #[test]
+    fn meld_with_empty_heaps() {
+        let mut heap = LeftistMaxHeap::new();
+        heap.meld(LeftistMaxHeap::new());
+        assert!(heap.is_empty());
+
+        heap.meld([3, 1, 2].into_iter().collect());
+        heap.meld(LeftistMaxHeap::new());
+        assert_eq!(heap.len(), 3);
+        assert_eq!(heap.into_sorted_vec(), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn big_heap_is_dropped() {
+        let heap: LeftistMaxHeap<i32> = (0..1_000_000).rev().collect();
+        drop(heap);
+    }
+
+    proptest! {
+        #[test]
+        fn melded_heaps_contain_all_values(
+            heaps in prop::collection::vec(prop::collection::vec(-100i32..100, 0..30), 0..10),
+        ) {
+            let mut melded = LeftistMinHeap::new();
+            for values in &heaps {
+                melded.meld(values.iter().cloned().collect());
+            }
+
+            let expected = heaps.concat().tap_mut(|vec| vec.sort_unstable());
+            prop_assert_eq!(melded.len(), expected.len());
+            prop_assert_eq!(melded.into_sorted_vec(), expected);
+        }
+    }
+}
@@ -0,0 +1,296 @@
+//! Double-ended priority queue based on a min-max heap
+
+use super::{MaxPriorityQueue, MinPriorityQueue};
+
+/// Binary heap whose even levels are ordered as a min-heap and odd levels as a max-heap,
+/// so both the smallest and the biggest values are available in O(1) and removable in O(log n)
+#[derive(Debug, Clone)]
+pub struct MinMaxHeap<T> {
+    data: Vec<T>,
+}
+
+impl<T: Ord> MinMaxHeap<T> {
+    pub fn new() -> Self {
+        Self { data: vec![] }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            data: Vec::with_capacity(capacity),
+        }
+    }
+
+    pub fn from_slice(data: &[T]) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_iter(data.iter().cloned())
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// values in ascending order
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut result = Vec::with_capacity(self.data.len());
+
+        while let Some(val) = self.pop_min() {
+            result.push(val);
+        }
+
+        result
+    }
+
+    fn _is_min_level(idx: usize) -> bool {
+        (idx + 1).ilog2().is_multiple_of(2)
+    }
+
+    /// check if `a` should be closer to the root than `b` on a level of a given kind
+    fn _before(a: &T, b: &T, min_level: bool) -> bool {
+        if min_level {
+            a < b
+        } else {
+            a > b
+        }
+    }
+
+    fn _max_idx(&self) -> Option<usize> {
+        match self.data.len() {
+            0 => None,
+            1 => Some(0),
+            2 => Some(1),
+            _ if self.data[2] > self.data[1] => Some(2),
+            _ => Some(1),
+        }
+    }
+
+    fn _bubble_up(&mut self, idx: usize) {
+        if idx == 0 {
+            return;
+        }
+
+        let parent_idx = (idx - 1) / 2;
+        let min_level = Self::_is_min_level(idx);
+
+        // a value which belongs to the other kind of levels is moved to the parent first
+        if Self::_before(&self.data[parent_idx], &self.data[idx], !min_level) {
+            self._bubble_up_through_grandparents(idx, min_level);
+        } else {
+            self.data.swap(idx, parent_idx);
+            self._bubble_up_through_grandparents(parent_idx, !min_level);
+        }
+    }
+
+    fn _bubble_up_through_grandparents(&mut self, mut idx: usize, min_level: bool) {
+        while idx > 2 {
+            let grandparent_idx = ((idx - 1) / 2 - 1) / 2;
+
+            if !Self::_before(&self.data[idx], &self.data[grandparent_idx], min_level) {
+                return;
+            }
+
+            self.data.swap(idx, grandparent_idx);
+            idx = grandparent_idx;
+        }
+    }
+
+    fn _trickle_down(&mut self, mut idx: usize) {
+        let min_level = Self::_is_min_level(idx);
+
+        loop {
+            // the best of children and grandchildren
+            let first_child = 2 * idx + 1;
+            let first_grandchild = 4 * idx + 3;
+            let descendants = (first_child..first_child + 2)
+                .chain(first_grandchild..first_grandchild + 4)
+                .take_while(|&i| i < self.data.len());
+
+            let Some(best_idx) = descendants.reduce(|best, i| {
+                if Self::_before(&self.data[i], &self.data[best], min_level) {
+                    i
+                } else {
+                    best
+                }
+            }) else {
+                return;
+            };
+
+            if !Self::_before(&self.data[best_idx], &self.data[idx], min_level) {
+                return;
+            }
+
+            self.data.swap(idx, best_idx);
+            if best_idx < first_grandchild {
+                return;
+            }
+
+            // the moved value may belong to the parent's level of the other kind
+            let parent_idx = (best_idx - 1) / 2;
+            if Self::_before(&self.data[parent_idx], &self.data[best_idx], min_level) {
+                self.data.swap(parent_idx, best_idx);
+            }
+            idx = best_idx;
+        }
+    }
+}
+
+impl<T: Ord> Default for MinMaxHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for MinMaxHeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut heap = Self::new();
+        for val in iter {
+            MinPriorityQueue::push(&mut heap, val);
+        }
+        heap
+    }
+}
+
+impl<T: Ord> MinPriorityQueue<T> for MinMaxHeap<T> {
+    fn min(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    fn pop_min(&mut self) -> Option<T> {
+        if self.data.is_empty() {
+            return None;
+        }
+
+        let min = self.data.swap_remove(0);
+        if !self.data.is_empty() {
+            self._trickle_down(0);
+        }
+
+        Some(min)
+    }
+
+    fn push(&mut self, val: T) {
+        let idx = self.data.len();
+        self.data.push(val);
+        self._bubble_up(idx);
+    }
+}
+
+impl<T: Ord> MaxPriorityQueue<T> for MinMaxHeap<T> {
+    fn max(&self) -> Option<&T> {
+        self._max_idx().map(|idx| &self.data[idx])
+    }
+
+    fn pop_max(&mut self) -> Option<T> {
+        let idx = self._max_idx()?;
+
+        let max = self.data.swap_remove(idx);
+        if idx < self.data.len() {
+            self._trickle_down(idx);
+        }
+
+        Some(max)
+    }
+
+    fn push(&mut self, val: T) {
+        MinPriorityQueue::push(self, val)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use rand::{Rng, SeedableRng};
+    use tap::Tap;
+
+    use super::*;
+
+    #[rstest::fixture]
+    fn rng() -> impl Rng {
+        rand::rngs::StdRng::from_seed([0; 32])
+    }
+
+    #[rstest::fixture]
+    fn data(rng: impl Rng) -> Vec<i32> {
+        let distr = rand::distributions::Uniform::new_inclusive(-1000, 1000);
+        rng.sample_iter(distr).take(1000).collect()
+    }
+
+    #[rstest::rstest]
+    fn min_and_max_peek_extremes(data: Vec<i32>) {
+        let heap: MinMaxHeap<i32> = data.iter().cloned().collect();
+        assert_eq!(heap.min(), data.iter().min());
+        assert_eq!(heap.max(), data.iter().max());
+        assert_eq!(heap.len(), data.len());
+    }
+
+    #[rstest::rstest]
+    fn pops_return_none_on_empty_heap() {
+        let mut heap: MinMaxHeap<i32> = MinMaxHeap::new();
+        assert_eq!(heap.pop_min(), None);
+        assert_eq!(heap.pop_max(), None);
+    }
+
+    #[rstest::rstest]
+    fn heap_is_sorted(data: Vec<i32>) {
+        let heap: MinMaxHeap<i32> = data.iter().cloned().collect();
+        let sorted = data.tap_mut(|vec| vec.sort_unstable());
+
+        assert_eq!(heap.into_sorted_vec(), sorted);
+    }
+
+    #[rstest::rstest]
+    fn pop_max_drains_in_descending_order(data: Vec<i32>) {
+        let mut heap: MinMaxHeap<i32> = data.iter().cloned().collect();
+        let sorted = data.tap_mut(|vec| vec.sort_unstable_by_key(|val| -val));
+
+        let drained: Vec<_> = std::iter::from_fn(|| heap.pop_max()).collect();
+        assert_eq!(drained, sorted);
+    }
+
+    #[derive(Debug, Clone)]
+    enum Op {
+        Push(i32),
+        PopMin,
+        PopMax,
+    }
+
+    fn op() -> impl Strategy<Value = Op> {
+        prop_oneof![
+            2 => (-100i32..100).prop_map(Op::Push),
+            1 => Just(Op::PopMin),
+            1 => Just(Op::PopMax),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn interleaved_operations_match_sorted_model(ops in prop::collection::vec(op(), 0..300)) {
+            let mut heap = MinMaxHeap::new();
+            // sorted in ascending order
+            let mut model: Vec<i32> = vec![];
+
+            for op in ops {
+                match op {
+                    Op::Push(val) => {
+                        MinPriorityQueue::push(&mut heap, val);
+                        let idx = model.partition_point(|&x| x < val);
+                        model.insert(idx, val);
+                    }
+                    Op::PopMin => {
+                        let expected = (!model.is_empty()).then(|| model.remove(0));
+                        prop_assert_eq!(heap.pop_min(), expected);
+                    }
+                    Op::PopMax => prop_assert_eq!(heap.pop_max(), model.pop()),
+                }
+
+                prop_assert_eq!(heap.min(), model.first());
+                prop_assert_eq!(heap.max(), model.last());
+            }
+        }
+    }
+}
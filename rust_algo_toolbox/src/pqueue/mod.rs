@@ -1,4 +1,10 @@
 pub mod dway_heap;
+pub mod indexed_heap;
+pub mod leftist_heap;
+pub mod min_max_heap;
+pub mod pairing_heap;
+pub mod sliding_window;
+pub mod top_k;
 
 pub trait MaxPriorityQueue<T> {
     fn max(&self) -> Option<&T>;
@@ -10,4 +16,4 @@ pub trait MinPriorityQueue<T> {
     fn min(&self) -> Option<&T>;
     fn pop_min(&mut self) -> Option<T>;
     fn push(&mut self, val: T);
-}
\ No newline at end of file
+}
@@ -0,0 +1,263 @@
+//! Pairing heap
+//!
+//! Heap-ordered multiway tree with O(1) `push` and `meld` and amortized O(log n) removal of the top value.
+//! The same heap is used as a min-heap or as a max-heap depending on its [`HeapOrder`]
+
+use std::{fmt::Debug, marker::PhantomData};
+
+use super::{
+    indexed_heap::{HeapOrder, MaxOrder, MinOrder},
+    MaxPriorityQueue, MinPriorityQueue,
+};
+
+struct Node<T> {
+    val: T,
+    children: Vec<Box<Node<T>>>,
+}
+
+/// Mergeable heap with O(1) meld
+pub struct PairingHeap<T, O> {
+    root: Option<Box<Node<T>>>,
+    len: usize,
+    order: PhantomData<O>,
+}
+
+/// Pairing min-heap
+pub type PairingMinHeap<T> = PairingHeap<T, MinOrder>;
+
+/// Pairing max-heap
+pub type PairingMaxHeap<T> = PairingHeap<T, MaxOrder>;
+
+impl<T: Ord, O: HeapOrder> Default for PairingHeap<T, O> {
+    fn default() -> Self {
+        Self {
+            root: None,
+            len: 0,
+            order: PhantomData,
+        }
+    }
+}
+
+impl<T: Ord, O: HeapOrder> PairingHeap<T, O> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn push(&mut self, val: T) {
+        let node = Box::new(Node {
+            val,
+            children: vec![],
+        });
+
+        self.root = Some(match self.root.take() {
+            Some(root) => Self::_link(root, node),
+            None => node,
+        });
+        self.len += 1;
+    }
+
+    /// value on top of the heap
+    pub fn peek(&self) -> Option<&T> {
+        self.root.as_ref().map(|root| &root.val)
+    }
+
+    /// remove the value on top of the heap
+    pub fn pop(&mut self) -> Option<T> {
+        let mut root = self.root.take()?;
+        self.len -= 1;
+
+        self.root = Self::_merge_pairs(std::mem::take(&mut root.children));
+
+        Some(root.val)
+    }
+
+    /// move all values of the other heap into this one in O(1)
+    pub fn meld(&mut self, mut other: Self) {
+        self.len += std::mem::take(&mut other.len);
+
+        self.root = match (self.root.take(), other.root.take()) {
+            (Some(a), Some(b)) => Some(Self::_link(a, b)),
+            (a, b) => a.or(b),
+        };
+    }
+
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut result = Vec::with_capacity(self.len);
+
+        while let Some(val) = self.pop() {
+            result.push(val);
+        }
+
+        result
+    }
+
+    /// make the tree with the lower priority root a child of the other one
+    fn _link(a: Box<Node<T>>, b: Box<Node<T>>) -> Box<Node<T>> {
+        let (mut parent, child) = if O::before(&b.val, &a.val) {
+            (b, a)
+        } else {
+            (a, b)
+        };
+
+        parent.children.push(child);
+        parent
+    }
+
+    /// standard two-pass pairing: link neighbours left to right, then fold the pairs right to left
+    fn _merge_pairs(children: Vec<Box<Node<T>>>) -> Option<Box<Node<T>>> {
+        let mut pairs = Vec::with_capacity(children.len() / 2 + 1);
+
+        let mut children = children.into_iter();
+        while let Some(first) = children.next() {
+            pairs.push(match children.next() {
+                Some(second) => Self::_link(first, second),
+                None => first,
+            });
+        }
+
+        pairs
+            .into_iter()
+            .rev()
+            .reduce(|acc, tree| Self::_link(tree, acc))
+    }
+}
+
+impl<T, O> Drop for PairingHeap<T, O> {
+    // trees may be as deep as the number of values, so they are dismantled without recursion
+    fn drop(&mut self) {
+        let mut stack: Vec<_> = self.root.take().into_iter().collect();
+
+        while let Some(mut node) = stack.pop() {
+            stack.append(&mut node.children);
+        }
+    }
+}
+
+impl<T: Ord + Debug, O: HeapOrder> Debug for PairingHeap<T, O> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PairingHeap")
+            .field("top", &self.peek())
+            .field("len", &self.len)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: Ord, O: HeapOrder> FromIterator<T> for PairingHeap<T, O> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut heap = Self::new();
+        for val in iter {
+            heap.push(val);
+        }
+        heap
+    }
+}
+
+impl<T: Ord> MinPriorityQueue<T> for PairingHeap<T, MinOrder> {
+    fn min(&self) -> Option<&T> {
+        self.peek()
+    }
+
+    fn pop_min(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    fn push(&mut self, val: T) {
+        PairingHeap::push(self, val)
+    }
+}
+
+impl<T: Ord> MaxPriorityQueue<T> for PairingHeap<T, MaxOrder> {
+    fn max(&self) -> Option<&T> {
+        self.peek()
+    }
+
+    fn pop_max(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    fn push(&mut self, val: T) {
+        PairingHeap::push(self, val)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use rand::{Rng, SeedableRng};
+    use tap::Tap;
+
+    use super::*;
+
+    #[rstest::fixture]
+    fn rng() -> impl Rng {
+        rand::rngs::StdRng::from_seed([0; 32])
+    }
+
+    #[rstest::fixture]
+    fn data(rng: impl Rng) -> Vec<i32> {
+        let distr = rand::distributions::Uniform::new_inclusive(-1000, 1000);
+        rng.sample_iter(distr).take(1000).collect()
+    }
+
+    #[rstest::rstest]
+    fn min_heap_is_sorted(data: Vec<i32>) {
+        let heap: PairingMinHeap<i32> = data.iter().cloned().collect();
+        assert_eq!(heap.min(), data.iter().min());
+        assert_eq!(heap.len(), data.len());
+
+        let sorted = data.tap_mut(|vec| vec.sort_unstable());
+        assert_eq!(heap.into_sorted_vec(), sorted);
+    }
+
+    #[rstest::rstest]
+    fn max_heap_is_sorted(data: Vec<i32>) {
+        let heap: PairingMaxHeap<i32> = data.iter().cloned().collect();
+        assert_eq!(heap.max(), data.iter().max());
+
+        let sorted = data.tap_mut(|vec| vec.sort_unstable_by_key(|val| -val));
+        assert_eq!(heap.into_sorted_vec(), sorted);
+    }
+
+    #[test]
+    fn meld_with_empty_heaps() {
+        let mut heap = PairingMinHeap::new();
+        heap.meld(PairingMinHeap::new());
+        assert!(heap.is_empty());
+
+        heap.meld([3, 1, 2].into_iter().collect());
+        heap.meld(PairingMinHeap::new());
+        assert_eq!(heap.len(), 3);
+        assert_eq!(heap.into_sorted_vec(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn deep_heap_is_dropped() {
+        // every pushed value becomes the new root, so the tree degenerates into a path
+        let heap: PairingMaxHeap<i32> = (0..1_000_000).collect();
+        drop(heap);
+    }
+
+    proptest! {
+        #[test]
+        fn melded_heaps_contain_all_values(
+            heaps in prop::collection::vec(prop::collection::vec(-100i32..100, 0..30), 0..10),
+        ) {
+            let mut melded = PairingMinHeap::new();
+            for values in &heaps {
+                melded.meld(values.iter().cloned().collect());
+            }
+
+            let expected = heaps.concat().tap_mut(|vec| vec.sort_unstable());
+            prop_assert_eq!(melded.len(), expected.len());
+            prop_assert_eq!(melded.into_sorted_vec(), expected);
+        }
+    }
+}
@@ -0,0 +1,134 @@
+//! Extremes of a sliding window
+//!
+//! A monotonic deque keeps only the values which can still become the extreme of the window,
+//! so every value is pushed and removed once and the extreme is available in O(1).
+//! Minimum or maximum is tracked depending on the [`HeapOrder`]
+
+use std::{collections::VecDeque, marker::PhantomData};
+
+use super::indexed_heap::{HeapOrder, MaxOrder, MinOrder};
+
+/// Extreme of the last `size` pushed values
+#[derive(Debug, Clone)]
+pub struct SlidingWindow<T, O> {
+    /// positions and values in the order of pushes.
+    /// Every value is strictly closer to the extreme than the ones behind it
+    deque: VecDeque<(usize, T)>,
+    size: usize,
+    pushed: usize,
+    order: PhantomData<O>,
+}
+
+/// Minimum of a sliding window
+pub type SlidingWindowMin<T> = SlidingWindow<T, MinOrder>;
+
+/// Maximum of a sliding window
+pub type SlidingWindowMax<T> = SlidingWindow<T, MaxOrder>;
+
+impl<T: Ord, O: HeapOrder> SlidingWindow<T, O> {
+    pub fn new(size: usize) -> Self {
+        assert!(size > 0, "window can't be empty");
+
+        Self {
+            deque: VecDeque::with_capacity(size),
+            size,
+            pushed: 0,
+            order: PhantomData,
+        }
+    }
+
+    /// number of values the window covers
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// check if the window has received `size` values yet
+    pub fn is_full(&self) -> bool {
+        self.pushed >= self.size
+    }
+
+    /// slide the window over a new value and return the new extreme
+    pub fn push(&mut self, val: T) -> &T {
+        while self
+            .deque
+            .back()
+            .is_some_and(|(_, last)| !O::before(last, &val))
+        {
+            self.deque.pop_back();
+        }
+
+        self.deque.push_back((self.pushed, val));
+        self.pushed += 1;
+
+        while self
+            .deque
+            .front()
+            .is_some_and(|(pos, _)| pos + self.size < self.pushed)
+        {
+            self.deque.pop_front();
+        }
+
+        self.peek().unwrap()
+    }
+
+    /// extreme of the window, or `None` if nothing was pushed yet
+    pub fn peek(&self) -> Option<&T> {
+        self.deque.front().map(|(_, val)| val)
+    }
+}
+
+impl<T: Ord + Clone, O: HeapOrder> SlidingWindow<T, O> {
+    /// extremes of all windows of a given size, in order of their positions
+    pub fn extremes(data: &[T], size: usize) -> Vec<T> {
+        let mut window = Self::new(size);
+
+        data.iter()
+            .filter_map(|val| {
+                let extreme = window.push(val.clone()).clone();
+                window.is_full().then_some(extreme)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(vec![1, 3, -1, -3, 5, 3, 6, 7], 3, vec![3, 3, 5, 5, 6, 7])]
+    #[case(vec![1], 1, vec![1])]
+    #[case(vec![1, 2], 3, vec![])]
+    #[case(vec![4, 4, 4, 2], 2, vec![4, 4, 4])]
+    fn window_maximums(#[case] data: Vec<i32>, #[case] size: usize, #[case] expected: Vec<i32>) {
+        assert_eq!(SlidingWindowMax::extremes(&data, size), expected);
+    }
+
+    #[test]
+    fn push_returns_window_minimum() {
+        let mut window = SlidingWindowMin::new(2);
+        assert_eq!(window.peek(), None);
+
+        assert_eq!(window.push(3), &3);
+        assert!(!window.is_full());
+        assert_eq!(window.push(5), &3);
+        assert!(window.is_full());
+        assert_eq!(window.push(4), &4);
+        assert_eq!(window.push(6), &4);
+        assert_eq!(window.push(7), &6);
+    }
+
+    proptest! {
+        #[test]
+        fn extremes_match_naive_scan(data in prop::collection::vec(-50i32..50, 0..100), size in 1usize..10) {
+            let expected_min: Vec<_> = data.windows(size).map(|w| *w.iter().min().unwrap()).collect();
+            let expected_max: Vec<_> = data.windows(size).map(|w| *w.iter().max().unwrap()).collect();
+
+            prop_assert_eq!(SlidingWindowMin::extremes(&data, size), expected_min);
+            prop_assert_eq!(SlidingWindowMax::extremes(&data, size), expected_max);
+        }
+    }
+}
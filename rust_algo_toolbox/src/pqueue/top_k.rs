@@ -0,0 +1,175 @@
+//! Bounded collector of the biggest values
+
+use std::marker::PhantomData;
+
+use super::{dway_heap::DWayMinHeap, MinPriorityQueue};
+
+/// Keeps at most `k` biggest of the pushed values.
+///
+/// The smallest kept value sits on top of a min-heap, so it can be evicted in O(log k)
+/// when a bigger value arrives. Wrap values in [`std::cmp::Reverse`] to keep the smallest ones instead
+#[derive(Debug, Clone)]
+pub struct TopK<T, Q = DWayMinHeap<T>> {
+    heap: Q,
+    len: usize,
+    k: usize,
+    values: PhantomData<T>,
+}
+
+impl<T: Ord, Q: MinPriorityQueue<T> + Default> TopK<T, Q> {
+    pub fn new(k: usize) -> Self {
+        Self {
+            heap: Q::default(),
+            len: 0,
+            k,
+            values: PhantomData,
+        }
+    }
+
+    /// maximum number of kept values
+    pub fn capacity(&self) -> usize {
+        self.k
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len == self.k
+    }
+
+    /// the smallest kept value, which is the next one to be evicted
+    pub fn threshold(&self) -> Option<&T> {
+        self.heap.min()
+    }
+
+    /// offer a value to the collector.
+    /// Returns the value which didn't make it to the top: either the evicted one or the pushed one itself
+    pub fn push(&mut self, val: T) -> Option<T> {
+        if self.len < self.k {
+            self.heap.push(val);
+            self.len += 1;
+            return None;
+        }
+
+        match self.heap.min() {
+            Some(min) if *min < val => {
+                let evicted = self.heap.pop_min();
+                self.heap.push(val);
+                evicted
+            }
+            _ => Some(val),
+        }
+    }
+
+    /// kept values from the biggest to the smallest
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut result = Vec::with_capacity(self.len);
+
+        while let Some(val) = self.heap.pop_min() {
+            result.push(val);
+        }
+        result.reverse();
+
+        result
+    }
+}
+
+impl<T: Ord, Q: MinPriorityQueue<T> + Default> Extend<T> for TopK<T, Q> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for val in iter {
+            self.push(val);
+        }
+    }
+}
+
+impl<T: Ord, Q: MinPriorityQueue<T> + Default> IntoIterator for TopK<T, Q> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    /// iterate over kept values from the biggest to the smallest
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_sorted_vec().into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Reverse;
+
+    use proptest::prelude::*;
+    use rand::{Rng, SeedableRng};
+    use tap::Tap;
+
+    use super::*;
+    use crate::pqueue::pairing_heap::PairingMinHeap;
+
+    #[rstest::fixture]
+    fn rng() -> impl Rng {
+        rand::rngs::StdRng::from_seed([0; 32])
+    }
+
+    #[rstest::fixture]
+    fn data(rng: impl Rng) -> Vec<i32> {
+        let distr = rand::distributions::Uniform::new_inclusive(-1000, 1000);
+        rng.sample_iter(distr).take(1000).collect()
+    }
+
+    #[rstest::rstest]
+    #[case(0)]
+    #[case(1)]
+    #[case(10)]
+    #[case(2000)]
+    fn keeps_biggest_values(data: Vec<i32>, #[case] k: usize) {
+        let mut top: TopK<i32> = TopK::new(k);
+        top.extend(data.iter().cloned());
+
+        let expected: Vec<_> = data
+            .tap_mut(|vec| vec.sort_unstable_by_key(|val| -val))
+            .into_iter()
+            .take(k)
+            .collect();
+        assert_eq!(top.len(), expected.len());
+        assert_eq!(top.into_sorted_vec(), expected);
+    }
+
+    #[rstest::rstest]
+    fn keeps_smallest_reversed_values(data: Vec<i32>) {
+        let mut top: TopK<Reverse<i32>, PairingMinHeap<_>> = TopK::new(5);
+        top.extend(data.iter().cloned().map(Reverse));
+
+        let expected = data.tap_mut(|vec| vec.sort_unstable())[..5].to_vec();
+        let result: Vec<_> = top.into_iter().map(|Reverse(val)| val).collect();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn push_returns_rejected_value() {
+        let mut top: TopK<i32> = TopK::new(2);
+
+        assert_eq!(top.push(5), None);
+        assert_eq!(top.push(1), None);
+        assert!(top.is_full());
+        assert_eq!(top.threshold(), Some(&1));
+
+        assert_eq!(top.push(3), Some(1));
+        assert_eq!(top.push(2), Some(2));
+        assert_eq!(top.threshold(), Some(&3));
+    }
+
+    proptest! {
+        #[test]
+        fn threshold_is_kth_biggest(values in prop::collection::vec(-100i32..100, 1..100), k in 1usize..20) {
+            let mut top: TopK<i32> = TopK::new(k);
+            top.extend(values.iter().cloned());
+
+            let sorted = values.tap_mut(|vec| vec.sort_unstable_by_key(|val| -val));
+            prop_assert_eq!(top.threshold(), sorted.get(k.min(sorted.len()) - 1));
+        }
+    }
+}
@@ -0,0 +1,333 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::trie::{
+    hash_trie::{HashTrie, HashTrieNode},
+    Trie,
+};
+
+const ROOT: usize = 0;
+
+/// Occurrence of a pattern in a text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Match {
+    /// index of the pattern in the set the automaton was built from
+    pub pattern: usize,
+    /// offset right after the last byte of the occurrence
+    pub end: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Node {
+    next: HashMap<u8, usize>,
+    /// the longest proper suffix of the node's string which is also a node
+    fail: usize,
+    /// the longest proper suffix of the node's string which is a pattern
+    dict_suffix: Option<usize>,
+    /// patterns equal to the node's string
+    patterns: Vec<usize>,
+}
+
+/// Aho–Corasick automaton finding occurrences of all patterns of a set in one pass over a text.
+///
+/// It's a trie of the patterns, built as a [`HashTrie`] and numbered in BFS order,
+/// where every node has a failure link to its longest suffix in the trie,
+/// so matching never goes back in the text, and a dictionary-suffix link to the longest suffix
+/// which is a pattern, so all patterns ending at a position are enumerated without visiting other nodes
+#[derive(Debug, Clone)]
+pub struct AhoCorasick {
+    nodes: Vec<Node>,
+    pattern_lens: Vec<usize>,
+    /// node reached by the bytes fed so far
+    state: usize,
+    fed: usize,
+}
+
+impl AhoCorasick {
+    pub fn new<P: AsRef<[u8]>>(patterns: impl IntoIterator<Item = P>) -> Self {
+        let patterns: Vec<P> = patterns.into_iter().collect();
+
+        let mut trie = HashTrie::new();
+        for pattern in &patterns {
+            trie.insert(pattern.as_ref().iter().copied());
+        }
+
+        let mut automaton = AhoCorasick {
+            nodes: number_nodes(trie.root()),
+            pattern_lens: vec![],
+            state: ROOT,
+            fed: 0,
+        };
+
+        for pattern in &patterns {
+            automaton.add_pattern(pattern.as_ref());
+        }
+        automaton.build_links();
+
+        automaton
+    }
+
+    pub fn patterns_count(&self) -> usize {
+        self.pattern_lens.len()
+    }
+
+    /// length of a pattern in bytes
+    pub fn pattern_len(&self, pattern: usize) -> usize {
+        self.pattern_lens[pattern]
+    }
+
+    /// all occurrences of the patterns in the text ordered by their end offsets.
+    /// Occurrences ending at the same offset are reported from the longest pattern to the shortest one
+    pub fn find_iter<'a>(&'a self, text: &'a [u8]) -> FindIter<'a> {
+        FindIter {
+            automaton: self,
+            text,
+            state: ROOT,
+            // empty patterns match before the first byte
+            matches: self.matches_at(ROOT, 0),
+        }
+    }
+
+    /// feed the next byte of a stream and return occurrences of the patterns ending at it
+    pub fn feed(&mut self, byte: u8) -> Matches<'_> {
+        self.state = self.step(self.state, byte);
+        self.fed += 1;
+
+        self.matches_at(self.state, self.fed)
+    }
+
+    /// forget the bytes fed so far
+    pub fn reset(&mut self) {
+        self.state = ROOT;
+        self.fed = 0;
+    }
+}
+
+// private
+impl AhoCorasick {
+    /// mark the node of a pattern, which is already in the trie
+    fn add_pattern(&mut self, pattern: &[u8]) {
+        let node = pattern
+            .iter()
+            .fold(ROOT, |node, byte| self.nodes[node].next[byte]);
+
+        self.nodes[node].patterns.push(self.pattern_lens.len());
+        self.pattern_lens.push(pattern.len());
+    }
+
+    /// compute failure and dictionary-suffix links in BFS order,
+    /// so links of shorter strings are known when they are needed
+    fn build_links(&mut self) {
+        let mut queue: VecDeque<usize> = self.nodes[ROOT].next.values().copied().collect();
+        for &child in &queue {
+            self.nodes[child].dict_suffix = self.pattern_node(ROOT);
+        }
+
+        while let Some(node) = queue.pop_front() {
+            let children: Vec<_> = self.nodes[node]
+                .next
+                .iter()
+                .map(|(&byte, &child)| (byte, child))
+                .collect();
+
+            for (byte, child) in children {
+                let fail = self.step(self.nodes[node].fail, byte);
+
+                self.nodes[child].fail = fail;
+                self.nodes[child].dict_suffix =
+                    self.pattern_node(fail).or(self.nodes[fail].dict_suffix);
+                queue.push_back(child);
+            }
+        }
+    }
+
+    fn pattern_node(&self, node: usize) -> Option<usize> {
+        (!self.nodes[node].patterns.is_empty()).then_some(node)
+    }
+
+    /// follow failure links until the byte can be matched
+    fn step(&self, mut node: usize, byte: u8) -> usize {
+        loop {
+            if let Some(&next) = self.nodes[node].next.get(&byte) {
+                return next;
+            }
+            if node == ROOT {
+                return ROOT;
+            }
+            node = self.nodes[node].fail;
+        }
+    }
+
+    fn matches_at(&self, node: usize, end: usize) -> Matches<'_> {
+        Matches {
+            automaton: self,
+            node: self.pattern_node(node).or(self.nodes[node].dict_suffix),
+            idx: 0,
+            end,
+        }
+    }
+}
+
+/// copy the trie into an arena with nodes numbered in BFS order, the root being [`ROOT`]
+fn number_nodes(root: &HashTrieNode<u8>) -> Vec<Node> {
+    let mut nodes = vec![Node::default()];
+    let mut queue = VecDeque::from([(root, ROOT)]);
+
+    while let Some((trie_node, node)) = queue.pop_front() {
+        for (&byte, trie_child) in trie_node.children() {
+            nodes.push(Node::default());
+            let child = nodes.len() - 1;
+
+            nodes[node].next.insert(byte, child);
+            queue.push_back((trie_child, child));
+        }
+    }
+
+    nodes
+}
+
+/// Occurrences of patterns ending at the same offset
+#[derive(Debug, Clone)]
+pub struct Matches<'a> {
+    automaton: &'a AhoCorasick,
+    /// node with patterns to report
+    node: Option<usize>,
+    idx: usize,
+    end: usize,
+}
+
+impl Iterator for Matches<'_> {
+    type Item = Match;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let node = &self.automaton.nodes[self.node?];
+
+            if let Some(&pattern) = node.patterns.get(self.idx) {
+                self.idx += 1;
+                return Some(Match {
+                    pattern,
+                    end: self.end,
+                });
+            }
+
+            self.node = node.dict_suffix;
+            self.idx = 0;
+        }
+    }
+}
+
+/// Iterator over occurrences of patterns in a text
+#[derive(Debug, Clone)]
+pub struct FindIter<'a> {
+    automaton: &'a AhoCorasick,
+    text: &'a [u8],
+    state: usize,
+    matches: Matches<'a>,
+}
+
+impl Iterator for FindIter<'_> {
+    type Item = Match;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(found) = self.matches.next() {
+                return Some(found);
+            }
+
+            let pos = self.matches.end;
+            let &byte = self.text.get(pos)?;
+
+            self.state = self.automaton.step(self.state, byte);
+            self.matches = self.automaton.matches_at(self.state, pos + 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    use super::*;
+
+    fn naive_find_all(patterns: &[&str], text: &str) -> Vec<Match> {
+        let mut result = vec![];
+
+        for end in 0..=text.len() {
+            for (pattern, pattern_str) in patterns.iter().enumerate() {
+                if text[..end].ends_with(pattern_str) {
+                    result.push(Match { pattern, end });
+                }
+            }
+        }
+
+        result
+    }
+
+    fn sorted(mut matches: Vec<Match>) -> Vec<Match> {
+        matches.sort_by_key(|found| (found.end, found.pattern));
+        matches
+    }
+
+    #[rstest]
+    #[case(&["he", "she", "his", "hers"], "ushers")]
+    #[case(&["a", "aa", "aaa"], "aaaa")]
+    #[case(&["abc", "bc", "c", "d"], "abcabd")]
+    #[case(&["x"], "")]
+    #[case(&["", "ab"], "abab")]
+    #[case(&["ab", "ab"], "xabx")]
+    fn finds_all_occurrences(#[case] patterns: &[&str], #[case] text: &str) {
+        let automaton = AhoCorasick::new(patterns);
+        let found: Vec<_> = automaton.find_iter(text.as_bytes()).collect();
+
+        assert_eq!(sorted(found), naive_find_all(patterns, text));
+    }
+
+    #[test]
+    fn longest_patterns_are_reported_first() {
+        let automaton = AhoCorasick::new(["he", "she", "his", "hers"]);
+        let found: Vec<_> = automaton.find_iter(b"ushers").collect();
+
+        assert_eq!(
+            found,
+            vec![
+                Match { pattern: 1, end: 4 },
+                Match { pattern: 0, end: 4 },
+                Match { pattern: 3, end: 6 },
+            ]
+        );
+        assert_eq!(automaton.pattern_len(3), 4);
+    }
+
+    #[test]
+    fn stream_reports_matches_ending_at_fed_byte() {
+        let mut automaton = AhoCorasick::new(["cd", "f", "kl"]);
+
+        let matched: Vec<_> = "abcdefghijkl"
+            .bytes()
+            .map(|byte| automaton.feed(byte).next().is_some())
+            .collect();
+        assert_eq!(
+            matched,
+            [false, false, false, true, false, true, false, false, false, false, false, true]
+        );
+
+        automaton.reset();
+        assert_eq!(automaton.feed(b'd').count(), 0);
+        assert_eq!(
+            automaton.feed(b'f').collect::<Vec<_>>(),
+            [Match { pattern: 1, end: 2 }]
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn matches_naive_search(patterns in prop::collection::vec("[ab]{1,4}", 1..6), text in "[abc]{0,40}") {
+            let patterns: Vec<&str> = patterns.iter().map(String::as_str).collect();
+            let automaton = AhoCorasick::new(&patterns);
+            let found: Vec<_> = automaton.find_iter(text.as_bytes()).collect();
+
+            prop_assert_eq!(sorted(found), naive_find_all(&patterns, &text));
+        }
+    }
+}
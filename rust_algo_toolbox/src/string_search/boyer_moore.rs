@@ -1,71 +1,139 @@
-use std::collections::HashMap;
+use super::{char_position, replace_all_str, Searcher};
+
+/// Boyer–Moore searcher with the bad character rule, precompiled for a pattern
+#[derive(Debug, Clone)]
+pub struct BoyerMoore {
+    pattern: Vec<u8>,
+    /// index of the last occurrence of every byte in the pattern
+    last_idx_table: [Option<usize>; 256],
+    /// index of the first occurrence of every byte in the pattern, used by reverse search
+    first_idx_table: [Option<usize>; 256],
+}
 
-pub fn boyer_moore(text: &str, pattern: &str) -> Option<usize> {
-    let Some(pos) = boyer_moore_slice(text.as_bytes(), pattern.as_bytes()) else {
-        return None;
-    };
+impl BoyerMoore {
+    pub fn new(pattern: impl AsRef<[u8]>) -> Self {
+        let pattern = pattern.as_ref().to_vec();
 
-    for (char_pos, (byte_pos, _)) in text.char_indices().enumerate() {
-        if byte_pos >= pos {
-            return Some(char_pos);
+        let mut last_idx_table = [None; 256];
+        let mut first_idx_table = [None; 256];
+        for (idx, &byte) in pattern.iter().enumerate() {
+            last_idx_table[byte as usize] = Some(idx);
+            first_idx_table[byte as usize].get_or_insert(idx);
         }
-    }
-
-    Some(text.len() - pattern.len())
-}
 
-pub fn boyer_moore_slice(text: &[u8], pattern: &[u8]) -> Option<usize> {
-    if pattern.is_empty() {
-        return Some(0);
+        BoyerMoore {
+            pattern,
+            last_idx_table,
+            first_idx_table,
+        }
     }
 
-    if text.len() < pattern.len() {
-        return None;
+    /// position of the last occurrence of the pattern
+    pub fn rfind(&self, text: &[u8]) -> Option<usize> {
+        self.rfind_before(text, text.len())
     }
 
-    let mut last_idx_table = HashMap::new();
-    for (idx, &byte) in pattern.iter().enumerate() {
-        last_idx_table
-            .entry(byte)
-            .and_modify(|pos| *pos = idx)
-            .or_insert(idx);
+    /// positions of all (possibly overlapping) occurrences from the right to the left
+    pub fn rfind_iter<'a>(&'a self, text: &'a [u8]) -> RFindIter<'a> {
+        RFindIter {
+            searcher: self,
+            text,
+            end: Some(text.len()),
+        }
     }
 
-    let mut start = 0;
-    while start <= text.len() - pattern.len() {
-        let mut found = true;
-        for pattern_idx in (0..pattern.len()).rev() {
-            let str_byte = text[start + pattern_idx];
+    /// the last occurrence ending at `end` or earlier
+    fn rfind_before(&self, text: &[u8], mut end: usize) -> Option<usize> {
+        let pattern = &self.pattern;
+
+        while end >= pattern.len() {
+            let start = end - pattern.len();
+            let mismatch = (0..pattern.len())
+                .find(|&pattern_idx| text[start + pattern_idx] != pattern[pattern_idx]);
 
-            if str_byte == pattern[pattern_idx] {
-                continue;
-            }
+            let Some(pattern_idx) = mismatch else {
+                return Some(start);
+            };
 
-            let Some(&byte_pos) = last_idx_table.get(&str_byte) else {
-                found = false;
-                start += pattern.len() - 1;
-                break;
+            // align the mismatched byte with its first occurrence in the pattern
+            let shift = match self.first_idx_table[text[start + pattern_idx] as usize] {
+                Some(byte_pos) if byte_pos > pattern_idx => byte_pos - pattern_idx,
+                Some(_) => 1,
+                None => pattern.len() - pattern_idx,
             };
+            end = end.checked_sub(shift)?;
+        }
 
-            if byte_pos > pattern_idx {
-                found = false;
-                break;
-            }
+        None
+    }
+}
 
-            found = false;
-            let shift = pattern_idx - byte_pos;
-            start += shift - 1;
-            break;
+impl Searcher for BoyerMoore {
+    fn pattern(&self) -> &[u8] {
+        &self.pattern
+    }
+
+    fn find_from(&self, text: &[u8], mut start: usize) -> Option<usize> {
+        if start > text.len() {
+            return None;
         }
 
-        if found {
-            return Some(start);
+        let pattern = &self.pattern;
+
+        while start + pattern.len() <= text.len() {
+            let mismatch = (0..pattern.len())
+                .rev()
+                .find(|&pattern_idx| text[start + pattern_idx] != pattern[pattern_idx]);
+
+            let Some(pattern_idx) = mismatch else {
+                return Some(start);
+            };
+
+            // align the mismatched byte with its last occurrence in the pattern
+            start += match self.last_idx_table[text[start + pattern_idx] as usize] {
+                Some(byte_pos) if byte_pos < pattern_idx => pattern_idx - byte_pos,
+                Some(_) => 1,
+                None => pattern_idx + 1,
+            };
         }
 
-        start += 1;
+        None
+    }
+}
+
+/// Iterator over positions of occurrences from the right to the left
+#[derive(Debug, Clone)]
+pub struct RFindIter<'a> {
+    searcher: &'a BoyerMoore,
+    text: &'a [u8],
+    end: Option<usize>,
+}
+
+impl Iterator for RFindIter<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pos = self.searcher.rfind_before(self.text, self.end?)?;
+        self.end = (pos + self.searcher.pattern.len()).checked_sub(1);
+
+        Some(pos)
     }
+}
+
+/// char position of the first occurrence of the pattern
+pub fn boyer_moore(text: &str, pattern: &str) -> Option<usize> {
+    let pos = boyer_moore_slice(text.as_bytes(), pattern.as_bytes())?;
+
+    Some(char_position(text, pos))
+}
+
+pub fn boyer_moore_slice(text: &[u8], pattern: &[u8]) -> Option<usize> {
+    BoyerMoore::new(pattern).find(text)
+}
 
-    None
+/// replace non-overlapping occurrences of the pattern found from the left to the right
+pub fn boyer_moore_replace_all(text: &str, pattern: &str, replacement: &str) -> String {
+    replace_all_str(&BoyerMoore::new(pattern), text, replacement)
 }
 
 #[cfg(test)]
@@ -0,0 +1,155 @@
+use super::Searcher;
+
+/// Prefix function of a string: for every prefix `s[..=i]`,
+/// the length of its longest proper prefix which is also its suffix
+pub fn prefix_function(s: &[u8]) -> Vec<usize> {
+    let mut result = vec![0; s.len()];
+
+    for i in 1..s.len() {
+        let mut len = result[i - 1];
+        while len > 0 && s[i] != s[len] {
+            len = result[len - 1];
+        }
+
+        if s[i] == s[len] {
+            len += 1;
+        }
+        result[i] = len;
+    }
+
+    result
+}
+
+/// Knuth–Morris–Pratt searcher precompiled for a pattern.
+/// Never goes back in the text, so the search takes O(n + m) time
+#[derive(Debug, Clone)]
+pub struct KnuthMorrisPratt {
+    pattern: Vec<u8>,
+    prefix: Vec<usize>,
+}
+
+impl KnuthMorrisPratt {
+    pub fn new(pattern: impl AsRef<[u8]>) -> Self {
+        let pattern = pattern.as_ref().to_vec();
+
+        KnuthMorrisPratt {
+            prefix: prefix_function(&pattern),
+            pattern,
+        }
+    }
+
+    /// prefix function of the pattern
+    pub fn prefix(&self) -> &[usize] {
+        &self.prefix
+    }
+
+    /// length of the longest prefix of the pattern matched after feeding a byte
+    /// to the state of the previous bytes
+    fn step(&self, mut matched: usize, byte: u8) -> usize {
+        if matched == self.pattern.len() {
+            matched = self.prefix[matched - 1];
+        }
+
+        while matched > 0 && self.pattern[matched] != byte {
+            matched = self.prefix[matched - 1];
+        }
+
+        if self.pattern[matched] == byte {
+            matched += 1;
+        }
+
+        matched
+    }
+}
+
+impl Searcher for KnuthMorrisPratt {
+    fn pattern(&self) -> &[u8] {
+        &self.pattern
+    }
+
+    fn find_from(&self, text: &[u8], start: usize) -> Option<usize> {
+        if start > text.len() {
+            return None;
+        }
+
+        let mut iter = KmpIter {
+            searcher: self,
+            text,
+            pos: start,
+            matched: 0,
+        };
+
+        iter.next()
+    }
+
+    fn find_iter<'a>(&'a self, text: &'a [u8]) -> impl Iterator<Item = usize> + 'a {
+        KmpIter {
+            searcher: self,
+            text,
+            pos: 0,
+            matched: 0,
+        }
+    }
+}
+
+/// Iterator over occurrences keeping the matched prefix between them
+#[derive(Debug, Clone)]
+struct KmpIter<'a> {
+    searcher: &'a KnuthMorrisPratt,
+    text: &'a [u8],
+    /// number of bytes of the text consumed
+    pos: usize,
+    /// length of the pattern's prefix ending at `pos`
+    matched: usize,
+}
+
+impl Iterator for KmpIter<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pattern_len = self.searcher.pattern.len();
+
+        if pattern_len == 0 {
+            // empty pattern matches at every position
+            self.pos += 1;
+            return (self.pos <= self.text.len() + 1).then(|| self.pos - 1);
+        }
+
+        while self.pos < self.text.len() {
+            self.matched = self.searcher.step(self.matched, self.text[self.pos]);
+            self.pos += 1;
+
+            if self.matched == pattern_len {
+                return Some(self.pos - pattern_len);
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(b"", vec![])]
+    #[case(b"a", vec![0])]
+    #[case(b"aabaaab", vec![0, 1, 0, 1, 2, 2, 3])]
+    #[case(b"abcabcd", vec![0, 0, 0, 1, 2, 3, 0])]
+    fn prefix_function_works(#[case] s: &[u8], #[case] expected: Vec<usize>) {
+        assert_eq!(prefix_function(s), expected);
+    }
+
+    #[test]
+    fn find_from_skips_earlier_matches() {
+        let searcher = KnuthMorrisPratt::new("aba");
+
+        assert_eq!(searcher.find_from(b"ababa", 0), Some(0));
+        assert_eq!(searcher.find_from(b"ababa", 1), Some(2));
+        assert_eq!(searcher.find_from(b"ababa", 3), None);
+        assert_eq!(searcher.find_from(b"ababa", 10), None);
+    }
+}
@@ -1,2 +1,221 @@
+pub mod aho_corasick;
 pub mod boyer_moore;
+pub mod knuth_morris_pratt;
 pub mod rabin_karp;
+pub mod rolling_hash;
+pub mod suffix_array;
+pub mod suffix_automaton;
+pub mod two_way;
+pub mod z_algorithm;
+
+/// Single pattern search algorithm precompiled for a pattern
+pub trait Searcher {
+    /// the pattern being searched for
+    fn pattern(&self) -> &[u8];
+
+    /// position of the first occurrence starting at `start` or later
+    fn find_from(&self, text: &[u8], start: usize) -> Option<usize>;
+
+    /// position of the first occurrence
+    fn find(&self, text: &[u8]) -> Option<usize> {
+        self.find_from(text, 0)
+    }
+
+    /// positions of all (possibly overlapping) occurrences from the left to the right
+    fn find_iter<'a>(&'a self, text: &'a [u8]) -> impl Iterator<Item = usize> + 'a {
+        SearchIter {
+            searcher: self,
+            text,
+            start: 0,
+        }
+    }
+
+    /// replace non-overlapping occurrences found from the left to the right
+    fn replace_all(&self, text: &[u8], replacement: &[u8]) -> Vec<u8> {
+        replace_matches(
+            text,
+            self.find_iter(text),
+            self.pattern().len(),
+            replacement,
+        )
+    }
+}
+
+/// Iterator over occurrences restarting the search after every match
+#[derive(Debug, Clone)]
+pub struct SearchIter<'a, S: ?Sized> {
+    searcher: &'a S,
+    text: &'a [u8],
+    start: usize,
+}
+
+impl<S: Searcher + ?Sized> Iterator for SearchIter<'_, S> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pos = self.searcher.find_from(self.text, self.start)?;
+        self.start = pos + 1;
+
+        Some(pos)
+    }
+}
+
+/// number of chars before a byte position
+fn char_position(text: &str, byte_pos: usize) -> usize {
+    text[..byte_pos].chars().count()
+}
+
+/// replace non-overlapping occurrences in a string found from the left to the right.
+/// Occurrences which don't start on a char boundary are skipped
+fn replace_all_str(searcher: &impl Searcher, text: &str, replacement: &str) -> String {
+    let matches = searcher
+        .find_iter(text.as_bytes())
+        .filter(|&pos| text.is_char_boundary(pos));
+
+    let replaced = replace_matches(
+        text.as_bytes(),
+        matches,
+        searcher.pattern().len(),
+        replacement.as_bytes(),
+    );
+    String::from_utf8(replaced).expect("matches of a valid pattern are on char boundaries")
+}
+
+/// replace non-overlapping matches given by their positions in ascending order.
+/// Matches overlapping the previously replaced one are skipped
+fn replace_matches(
+    text: &[u8],
+    matches: impl Iterator<Item = usize>,
+    pattern_len: usize,
+    replacement: &[u8],
+) -> Vec<u8> {
+    let mut result = Vec::with_capacity(text.len());
+    let mut copied = 0;
+
+    for pos in matches {
+        if pos < copied {
+            continue;
+        }
+
+        result.extend_from_slice(&text[copied..pos]);
+        result.extend_from_slice(replacement);
+        copied = pos + pattern_len;
+    }
+    result.extend_from_slice(&text[copied..]);
+
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    use super::{
+        boyer_moore::{boyer_moore_replace_all, BoyerMoore},
+        knuth_morris_pratt::KnuthMorrisPratt,
+        rabin_karp::{rabin_karp_replace_all, RabinKarp},
+        two_way::TwoWay,
+        z_algorithm::ZAlgorithm,
+        Searcher,
+    };
+
+    fn naive_find_all(text: &[u8], pattern: &[u8]) -> Vec<usize> {
+        (0..=text.len().saturating_sub(pattern.len()))
+            .filter(|&pos| text[pos..].starts_with(pattern))
+            .collect()
+    }
+
+    fn assert_searches<S: Searcher>(searcher: S, text: &[u8]) {
+        let expected = naive_find_all(text, searcher.pattern());
+
+        assert_eq!(searcher.find_iter(text).collect::<Vec<_>>(), expected);
+        assert_eq!(searcher.find(text), expected.first().copied());
+        for start in 0..=text.len() {
+            let next = expected.iter().find(|&&pos| pos >= start).copied();
+            assert_eq!(searcher.find_from(text, start), next);
+        }
+        assert_eq!(searcher.find_from(text, usize::MAX), None);
+    }
+
+    /// searchers with reverse search find the same occurrences in reversed order
+    fn assert_reverse_searches(pattern: &[u8], text: &[u8]) {
+        let expected: Vec<_> = naive_find_all(text, pattern).into_iter().rev().collect();
+
+        let boyer_moore = BoyerMoore::new(pattern);
+        assert_eq!(boyer_moore.rfind_iter(text).collect::<Vec<_>>(), expected);
+        assert_eq!(boyer_moore.rfind(text), expected.first().copied());
+
+        let rabin_karp = RabinKarp::new(pattern);
+        assert_eq!(rabin_karp.rfind_iter(text).collect::<Vec<_>>(), expected);
+        assert_eq!(rabin_karp.rfind(text), expected.first().copied());
+    }
+
+    fn assert_all_searchers(pattern: &[u8], text: &[u8]) {
+        assert_searches(BoyerMoore::new(pattern), text);
+        assert_searches(RabinKarp::new(pattern), text);
+        assert_searches(KnuthMorrisPratt::new(pattern), text);
+        assert_searches(ZAlgorithm::new(pattern), text);
+        assert_searches(TwoWay::new(pattern), text);
+        assert_reverse_searches(pattern, text);
+    }
+
+    fn assert_replaces<S: Searcher>(searcher: S, text: &str, replacement: &str, expected: &str) {
+        assert_eq!(
+            searcher.replace_all(text.as_bytes(), replacement.as_bytes()),
+            expected.as_bytes()
+        );
+    }
+
+    #[rstest]
+    #[case("", "")]
+    #[case("", "abc")]
+    #[case("abc", "")]
+    #[case("needle", "haystack with a needle and another needle")]
+    #[case("aa", "aaaaa")]
+    #[case("abc", "abcabc")]
+    #[case("cab", "abcabc")]
+    #[case("abab", "abababcabab")]
+    #[case("banana", "bananananabanana")]
+    #[case("long pattern", "short")]
+    fn searchers_agree(#[case] pattern: &str, #[case] text: &str) {
+        assert_all_searchers(pattern.as_bytes(), text.as_bytes());
+    }
+
+    #[rstest]
+    #[case("aaaa", "aa", "b", "bb")]
+    #[case("aaaaa", "aa", "b", "bba")]
+    #[case("abÁcdËÜfËÜ", "ËÜ", "E", "abÁcdEfE")]
+    #[case("hello", "x", "y", "hello")]
+    #[case("ab", "", "-", "-a-b-")]
+    #[case("ËÜ", "", "-", "-Ë-Ü-")]
+    #[case("", "", "x", "x")]
+    fn replace_all_skips_overlapping_matches(
+        #[case] text: &str,
+        #[case] pattern: &str,
+        #[case] replacement: &str,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(
+            boyer_moore_replace_all(text, pattern, replacement),
+            expected
+        );
+        assert_eq!(rabin_karp_replace_all(text, pattern, replacement), expected);
+
+        // byte searchers match the empty pattern inside chars too
+        if !pattern.is_empty() {
+            assert_replaces(BoyerMoore::new(pattern), text, replacement, expected);
+            assert_replaces(RabinKarp::new(pattern), text, replacement, expected);
+            assert_replaces(KnuthMorrisPratt::new(pattern), text, replacement, expected);
+            assert_replaces(ZAlgorithm::new(pattern), text, replacement, expected);
+            assert_replaces(TwoWay::new(pattern), text, replacement, expected);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn searchers_match_naive_search(pattern in "[ab]{0,5}", text in "[abc]{0,40}") {
+            assert_all_searchers(pattern.as_bytes(), text.as_bytes());
+        }
+    }
+}
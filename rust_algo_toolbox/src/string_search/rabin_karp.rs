@@ -1,77 +1,348 @@
-const BASE: usize = 256;
-const BIG_PRIME: usize = 2 << 31 - 1;
+use std::collections::HashMap;
 
-fn hash(s: &[u8]) -> usize {
-    let mut result = 0;
-    for &byte in s {
-        result = (result * 256 + (byte as usize)) % BIG_PRIME;
-    }
+use super::{
+    aho_corasick::Match,
+    char_position, replace_all_str,
+    rolling_hash::{RollingHash, WindowHashes},
+    Searcher,
+};
+
+/// rolling hash of the bytes in reversed order, which slides to the left
+fn reversed_window(window: &[u8]) -> RollingHash {
+    let mut result = RollingHash::new();
+    result.extend(window.iter().rev().map(|&byte| u64::from(byte)));
 
     result
 }
 
-fn pow(x: usize, p: usize) -> usize {
-    let mut result = 1;
+/// Rabin–Karp searcher precompiled for a pattern
+#[derive(Debug, Clone)]
+pub struct RabinKarp {
+    pattern: Vec<u8>,
+    pattern_hash: (u64, u64),
+    /// hash of the reversed pattern, used by reverse search
+    reversed_hash: (u64, u64),
+}
+
+impl RabinKarp {
+    pub fn new(pattern: impl AsRef<[u8]>) -> Self {
+        let pattern = pattern.as_ref().to_vec();
 
-    for _ in 0..p {
-        result = (result * x) % BIG_PRIME;
+        RabinKarp {
+            pattern_hash: RollingHash::from_bytes(&pattern).hash(),
+            reversed_hash: reversed_window(&pattern).hash(),
+            pattern,
+        }
     }
 
-    result
+    /// position of the last occurrence of the pattern
+    pub fn rfind(&self, text: &[u8]) -> Option<usize> {
+        self.rfind_iter(text).next()
+    }
+
+    /// positions of all (possibly overlapping) occurrences from the right to the left
+    pub fn rfind_iter<'a>(&'a self, text: &'a [u8]) -> RFindIter<'a> {
+        RFindIter {
+            searcher: self,
+            text,
+            end: Some(text.len()),
+            window_hash: None,
+        }
+    }
 }
 
-pub fn rabin_karp(text: &str, pattern: &str) -> Option<usize> {
-    let Some(pos) = rabin_karp_slice(text.as_bytes(), pattern.as_bytes()) else {
-        return None;
-    };
+impl Searcher for RabinKarp {
+    fn pattern(&self) -> &[u8] {
+        &self.pattern
+    }
+
+    fn find_from(&self, text: &[u8], start: usize) -> Option<usize> {
+        let pos = self.find_iter(text.get(start..)?).next()?;
+
+        Some(start + pos)
+    }
 
-    for (char_pos, (byte_pos, _)) in text.char_indices().enumerate() {
-        if byte_pos >= pos {
-            return Some(char_pos);
+    fn find_iter<'a>(&'a self, text: &'a [u8]) -> impl Iterator<Item = usize> + 'a {
+        FindIter {
+            searcher: self,
+            text,
+            start: 0,
+            hashes: WindowHashes::new(text, self.pattern.len()),
         }
     }
+}
 
-    Some(text.len() - pattern.len())
+/// Iterator over positions of occurrences from the left to the right,
+/// comparing bytes only when the window hash matches
+#[derive(Debug, Clone)]
+struct FindIter<'a> {
+    searcher: &'a RabinKarp,
+    text: &'a [u8],
+    start: usize,
+    hashes: WindowHashes<'a>,
 }
 
-pub fn rabin_karp_slice(text: &[u8], pattern: &[u8]) -> Option<usize> {
-    if pattern.is_empty() {
-        return Some(0);
+impl Iterator for FindIter<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pattern = &self.searcher.pattern;
+
+        for window_hash in self.hashes.by_ref() {
+            let start = self.start;
+            self.start += 1;
+
+            if window_hash == self.searcher.pattern_hash
+                && &self.text[start..start + pattern.len()] == pattern
+            {
+                return Some(start);
+            }
+        }
+
+        None
+    }
+}
+
+/// Iterator over positions of occurrences from the right to the left
+#[derive(Debug, Clone)]
+pub struct RFindIter<'a> {
+    searcher: &'a RabinKarp,
+    text: &'a [u8],
+    end: Option<usize>,
+    /// hash of the reversed window ending at `end`, computed lazily
+    window_hash: Option<RollingHash>,
+}
+
+impl Iterator for RFindIter<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pattern = &self.searcher.pattern;
+
+        while let Some(end) = self.end.filter(|&end| end >= pattern.len()) {
+            let start = end - pattern.len();
+            let window = &self.text[start..end];
+            let window_hash = self
+                .window_hash
+                .take()
+                .unwrap_or_else(|| reversed_window(window));
+
+            self.end = end.checked_sub(1);
+            if let (Some(&removed), Some(added)) = (window.last(), start.checked_sub(1)) {
+                let mut next_hash = window_hash;
+                next_hash.slide(removed.into(), self.text[added].into());
+                self.window_hash = Some(next_hash);
+            }
+
+            if window_hash.hash() == self.searcher.reversed_hash && window == pattern {
+                return Some(start);
+            }
+        }
+
+        None
     }
+}
+
+/// Rabin–Karp search of many patterns of the same length in one pass over a text.
+/// Patterns are looked up by the hash of every window, so the search takes O(n) expected time
+/// regardless of the number of patterns
+#[derive(Debug, Clone)]
+pub struct MultiRabinKarp {
+    patterns: Vec<Vec<u8>>,
+    pattern_len: usize,
+    /// indices of the patterns with a given hash
+    by_hash: HashMap<(u64, u64), Vec<usize>>,
+}
+
+impl MultiRabinKarp {
+    pub fn new<P: AsRef<[u8]>>(patterns: impl IntoIterator<Item = P>) -> Self {
+        let patterns: Vec<Vec<u8>> = patterns
+            .into_iter()
+            .map(|pattern| pattern.as_ref().to_vec())
+            .collect();
+        let pattern_len = patterns.first().map_or(0, Vec::len);
+        assert!(
+            patterns.iter().all(|pattern| pattern.len() == pattern_len),
+            "patterns must have the same length"
+        );
+
+        let mut by_hash: HashMap<_, Vec<_>> = HashMap::new();
+        for (idx, pattern) in patterns.iter().enumerate() {
+            let hash = RollingHash::from_bytes(pattern).hash();
+            by_hash.entry(hash).or_default().push(idx);
+        }
 
-    if text.len() < pattern.len() {
-        return None;
+        MultiRabinKarp {
+            patterns,
+            pattern_len,
+            by_hash,
+        }
     }
 
-    let base_pow_n = pow(BASE, pattern.len() - 1);
+    pub fn patterns_count(&self) -> usize {
+        self.patterns.len()
+    }
 
-    let pattern_hash = hash(pattern);
-    let mut substring_hash = hash(&text[..pattern.len()]);
+    /// length of every pattern in bytes
+    pub fn pattern_len(&self) -> usize {
+        self.pattern_len
+    }
 
-    if substring_hash == pattern_hash {
-        if pattern == &text[..pattern.len()] {
-            return Some(0);
+    /// all occurrences of the patterns in the text ordered by their positions.
+    /// Occurrences at the same position are ordered by pattern indices
+    pub fn find_iter<'a>(&'a self, text: &'a [u8]) -> MultiFindIter<'a> {
+        MultiFindIter {
+            searcher: self,
+            text,
+            hashes: WindowHashes::new(text, self.pattern_len),
+            next_start: 0,
+            candidates: &[],
         }
     }
+}
 
-    for start_idx in 1..=(text.len() - pattern.len()) {
-        let removed_char = text[start_idx - 1] as usize;
-        let added_char = text[start_idx + pattern.len() - 1] as usize;
+/// Iterator over occurrences of equal-length patterns in a text
+#[derive(Debug, Clone)]
+pub struct MultiFindIter<'a> {
+    searcher: &'a MultiRabinKarp,
+    text: &'a [u8],
+    hashes: WindowHashes<'a>,
+    next_start: usize,
+    /// patterns with the hash of the last hashed window, which are not checked yet
+    candidates: &'a [usize],
+}
 
-        // removing previous character (`BIG_PRIME` is added to avoid underflow)
-        substring_hash = substring_hash + removed_char * (BIG_PRIME - base_pow_n);
+impl Iterator for MultiFindIter<'_> {
+    type Item = Match;
 
-        // adding next character
-        substring_hash = (substring_hash * BASE + added_char) % BIG_PRIME;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            while let Some((&pattern, rest)) = self.candidates.split_first() {
+                self.candidates = rest;
 
-        if substring_hash == pattern_hash {
-            if pattern == &text[start_idx..(start_idx + pattern.len())] {
-                return Some(start_idx);
+                let start = self.next_start - 1;
+                let end = start + self.searcher.pattern_len;
+                if self.searcher.patterns[pattern] == self.text[start..end] {
+                    return Some(Match { pattern, end });
+                }
             }
+
+            let hash = self.hashes.next()?;
+            self.next_start += 1;
+            self.candidates = self.searcher.by_hash.get(&hash).map_or(&[], Vec::as_slice);
         }
     }
+}
 
-    None
+/// Rabin–Karp search of a rectangular pattern in a rectangular grid of bytes.
+/// Windows of every grid row are hashed as in the 1D search, then windows of these hashes
+/// are hashed down every column, so the search takes O(rows * cols) expected time
+#[derive(Debug, Clone)]
+pub struct RabinKarp2D {
+    pattern: Vec<Vec<u8>>,
+    width: usize,
+    pattern_hash: (u64, u64),
+}
+
+/// value fed to the column hash for a hash of a row window
+fn row_value((first, second): (u64, u64)) -> u64 {
+    first ^ second.rotate_left(32)
+}
+
+impl RabinKarp2D {
+    pub fn new<R: AsRef<[u8]>>(pattern: &[R]) -> Self {
+        let pattern: Vec<Vec<u8>> = pattern.iter().map(|row| row.as_ref().to_vec()).collect();
+        let width = pattern.first().map_or(0, Vec::len);
+        assert!(
+            pattern.iter().all(|row| row.len() == width),
+            "pattern must be rectangular"
+        );
+
+        let mut pattern_hash = RollingHash::new();
+        pattern_hash.extend(
+            pattern
+                .iter()
+                .map(|row| row_value(RollingHash::from_bytes(row).hash())),
+        );
+
+        RabinKarp2D {
+            pattern,
+            width,
+            pattern_hash: pattern_hash.hash(),
+        }
+    }
+
+    /// number of rows of the pattern
+    pub fn height(&self) -> usize {
+        self.pattern.len()
+    }
+
+    /// number of columns of the pattern
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// rows and columns of top-left corners of all occurrences in the row-major order
+    pub fn find_all<R: AsRef<[u8]>>(&self, grid: &[R]) -> Vec<(usize, usize)> {
+        let rows: Vec<&[u8]> = grid.iter().map(AsRef::as_ref).collect();
+        let grid_width = rows.first().map_or(0, |row| row.len());
+        assert!(
+            rows.iter().all(|row| row.len() == grid_width),
+            "grid must be rectangular"
+        );
+
+        let height = self.height();
+        if height > rows.len() || self.width > grid_width {
+            return vec![];
+        }
+
+        // `row_hashes[row][col]` is the value of the window of `width` bytes at `col`
+        let row_hashes: Vec<Vec<u64>> = rows
+            .iter()
+            .map(|row| WindowHashes::new(row, self.width).map(row_value).collect())
+            .collect();
+        let mut column_hashes = vec![RollingHash::new(); grid_width - self.width + 1];
+
+        let mut result = vec![];
+        for top in 0..=rows.len() - height {
+            for (col, column_hash) in column_hashes.iter_mut().enumerate() {
+                if top == 0 {
+                    column_hash.extend(row_hashes[..height].iter().map(|hashes| hashes[col]));
+                } else if height > 0 {
+                    column_hash.slide(row_hashes[top - 1][col], row_hashes[top + height - 1][col]);
+                }
+
+                if column_hash.hash() == self.pattern_hash && self.matches_at(&rows, top, col) {
+                    result.push((top, col));
+                }
+            }
+        }
+
+        result
+    }
+
+    fn matches_at(&self, rows: &[&[u8]], top: usize, col: usize) -> bool {
+        self.pattern
+            .iter()
+            .zip(&rows[top..])
+            .all(|(pattern_row, row)| pattern_row[..] == row[col..col + self.width])
+    }
+}
+
+/// char position of the first occurrence of the pattern
+pub fn rabin_karp(text: &str, pattern: &str) -> Option<usize> {
+    let pos = rabin_karp_slice(text.as_bytes(), pattern.as_bytes())?;
+
+    Some(char_position(text, pos))
+}
+
+pub fn rabin_karp_slice(text: &[u8], pattern: &[u8]) -> Option<usize> {
+    RabinKarp::new(pattern).find(text)
+}
+
+/// replace non-overlapping occurrences of the pattern found from the left to the right
+pub fn rabin_karp_replace_all(text: &str, pattern: &str, replacement: &str) -> String {
+    replace_all_str(&RabinKarp::new(pattern), text, replacement)
 }
 
 #[cfg(test)]
@@ -79,6 +350,7 @@ mod test {
     use crate::assert_returns;
 
     use super::*;
+    use proptest::prelude::*;
     use rstest::rstest;
 
     #[rstest]
@@ -130,4 +402,88 @@ mod test {
     ) {
         assert_returns!(expected, rabin_karp, text, pattern);
     }
+
+    fn naive_find_all(patterns: &[&str], text: &str) -> Vec<Match> {
+        let mut result = vec![];
+
+        for start in 0..=text.len() {
+            for (pattern, pattern_str) in patterns.iter().enumerate() {
+                if text[start..].starts_with(pattern_str) {
+                    let end = start + pattern_str.len();
+                    result.push(Match { pattern, end });
+                }
+            }
+        }
+
+        result
+    }
+
+    fn naive_find_2d(pattern: &[String], grid: &[String]) -> Vec<(usize, usize)> {
+        let height = pattern.len();
+        let width = pattern.first().map_or(0, String::len);
+        let grid_width = grid.first().map_or(0, String::len);
+        let mut result = vec![];
+
+        for top in 0..(grid.len() + 1).saturating_sub(height) {
+            for col in 0..(grid_width + 1).saturating_sub(width) {
+                if (0..height).all(|row| grid[top + row][col..col + width] == pattern[row]) {
+                    result.push((top, col));
+                }
+            }
+        }
+
+        result
+    }
+
+    #[rstest]
+    #[case(&["AC", "CA", "GG"], "ACACGGCA")]
+    #[case(&["aa", "aa"], "aaa")]
+    #[case(&["ab"], "")]
+    #[case(&["", ""], "ab")]
+    fn multi_pattern_finds_all_occurrences(#[case] patterns: &[&str], #[case] text: &str) {
+        let searcher = MultiRabinKarp::new(patterns);
+        let found: Vec<_> = searcher.find_iter(text.as_bytes()).collect();
+
+        assert_eq!(found, naive_find_all(patterns, text));
+    }
+
+    #[test]
+    #[should_panic]
+    fn multi_pattern_requires_equal_lengths() {
+        MultiRabinKarp::new(["ab", "abc"]);
+    }
+
+    #[rstest]
+    #[case(&["ab", "cd"], &["xabx", "xcdx", "abab", "cdcd"], vec![(0, 1), (2, 0), (2, 2)])]
+    #[case(&["aa", "aa"], &["aaa", "aaa", "aaa"], vec![(0, 0), (0, 1), (1, 0), (1, 1)])]
+    #[case(&["abc"], &["ab", "ab"], vec![])]
+    #[case(&["a", "a", "a"], &["a", "a"], vec![])]
+    fn grid_search_finds_all_occurrences(
+        #[case] pattern: &[&str],
+        #[case] grid: &[&str],
+        #[case] expected: Vec<(usize, usize)>,
+    ) {
+        assert_eq!(RabinKarp2D::new(pattern).find_all(grid), expected);
+    }
+
+    proptest! {
+        #[test]
+        fn multi_pattern_matches_naive_search(len in 1usize..4, patterns in prop::collection::vec("[ab]{4}", 1..5), text in "[ab]{0,30}") {
+            let patterns: Vec<&str> = patterns.iter().map(|pattern| &pattern[..len]).collect();
+            let searcher = MultiRabinKarp::new(&patterns);
+
+            prop_assert_eq!(searcher.find_iter(text.as_bytes()).collect::<Vec<_>>(), naive_find_all(&patterns, &text));
+        }
+
+        #[test]
+        fn grid_search_matches_naive_search(
+            (height, width) in (0usize..3, 0usize..3),
+            pattern in prop::collection::vec("[ab]{3}", 3),
+            grid in prop::collection::vec("[ab]{5}", 0..6),
+        ) {
+            let pattern: Vec<String> = pattern[..height].iter().map(|row| row[..width].to_owned()).collect();
+
+            prop_assert_eq!(RabinKarp2D::new(&pattern).find_all(&grid), naive_find_2d(&pattern, &grid));
+        }
+    }
 }
@@ -0,0 +1,241 @@
+//! Polynomial rolling hash
+//!
+//! The hash of a sequence `v[0], ..., v[n-1]` is `v[0] * b^(n-1) + ... + v[n-1]` modulo `m`.
+//! Appending a value and removing the first one are both O(1), so hashes of all windows
+//! of a text are computed in one pass. Two independent hashes are kept to make collisions unlikely
+
+/// base and modulus of the default hashes
+pub const DEFAULT_PARAMS: [(u64, u64); 2] = [(257, 1_000_000_007), (263, 998_244_353)];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Lane {
+    base: u64,
+    modulus: u64,
+    /// `base` to the power of `len - 1`, the weight of the first value
+    high_pow: u64,
+    hash: u64,
+}
+
+impl Lane {
+    fn new(base: u64, modulus: u64) -> Self {
+        assert!(modulus > 1, "modulus must be greater than 1");
+        assert!(
+            base > 0 && base < modulus,
+            "base must be in range 1..modulus"
+        );
+
+        Lane {
+            base,
+            modulus,
+            high_pow: 0,
+            hash: 0,
+        }
+    }
+
+    fn mul(&self, a: u64, b: u64) -> u64 {
+        (a as u128 * b as u128 % self.modulus as u128) as u64
+    }
+
+    fn add(&self, a: u64, b: u64) -> u64 {
+        ((a as u128 + b as u128) % self.modulus as u128) as u64
+    }
+
+    fn push(&mut self, value: u64, was_empty: bool) {
+        let value = value % self.modulus;
+
+        self.hash = self.add(self.mul(self.hash, self.base), value);
+        self.high_pow = if was_empty {
+            1
+        } else {
+            self.mul(self.high_pow, self.base)
+        };
+    }
+
+    fn slide(&mut self, removed: u64, added: u64) {
+        let removed = self.mul(removed % self.modulus, self.high_pow);
+        let hash = self.add(self.hash, self.modulus - removed);
+
+        self.hash = self.add(self.mul(hash, self.base), added % self.modulus);
+    }
+}
+
+/// Double polynomial hash of a window which can grow and slide in O(1)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RollingHash {
+    lanes: [Lane; 2],
+    len: usize,
+}
+
+impl RollingHash {
+    /// empty window hashed with [`DEFAULT_PARAMS`]
+    pub fn new() -> Self {
+        Self::with_params(DEFAULT_PARAMS)
+    }
+
+    /// empty window hashed with two `(base, modulus)` pairs.
+    /// Every base must be in range `1..modulus`
+    pub fn with_params(params: [(u64, u64); 2]) -> Self {
+        RollingHash {
+            lanes: params.map(|(base, modulus)| Lane::new(base, modulus)),
+            len: 0,
+        }
+    }
+
+    /// hash of bytes with [`DEFAULT_PARAMS`]
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        let mut result = Self::new();
+        result.extend(bytes.iter().map(|&byte| u64::from(byte)));
+
+        result
+    }
+
+    /// number of values in the window
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// the two hashes of the window
+    pub fn hash(&self) -> (u64, u64) {
+        (self.lanes[0].hash, self.lanes[1].hash)
+    }
+
+    /// append a value to the window
+    pub fn push(&mut self, value: u64) {
+        for lane in &mut self.lanes {
+            lane.push(value, self.len == 0);
+        }
+        self.len += 1;
+    }
+
+    /// remove the first value of the window and append a new one.
+    /// `removed` must be the value pushed `len` values ago
+    pub fn slide(&mut self, removed: u64, added: u64) {
+        assert!(!self.is_empty(), "can't slide an empty window");
+
+        for lane in &mut self.lanes {
+            lane.slide(removed, added);
+        }
+    }
+
+    /// empty the window keeping the hash parameters
+    pub fn clear(&mut self) {
+        for lane in &mut self.lanes {
+            (lane.hash, lane.high_pow) = (0, 0);
+        }
+        self.len = 0;
+    }
+}
+
+impl Default for RollingHash {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Extend<u64> for RollingHash {
+    fn extend<I: IntoIterator<Item = u64>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+/// Hashes of all windows of a given length in order of their positions
+#[derive(Debug, Clone)]
+pub struct WindowHashes<'a> {
+    text: &'a [u8],
+    len: usize,
+    /// hash of the window at `start`, computed lazily
+    window: Option<RollingHash>,
+    start: usize,
+}
+
+impl<'a> WindowHashes<'a> {
+    pub fn new(text: &'a [u8], len: usize) -> Self {
+        WindowHashes {
+            text,
+            len,
+            window: None,
+            start: 0,
+        }
+    }
+}
+
+impl Iterator for WindowHashes<'_> {
+    type Item = (u64, u64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.start;
+        let window = self.text.get(start..start + self.len)?;
+        self.start += 1;
+
+        let hash = match (self.window.as_mut(), window.last()) {
+            (Some(hash), Some(&added)) => {
+                hash.slide(self.text[start - 1].into(), added.into());
+                hash
+            }
+            // empty windows can't slide, so their hash is computed every time
+            _ => self.window.insert(RollingHash::from_bytes(window)),
+        };
+
+        Some(hash.hash())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn hash_is_polynomial() {
+        let mut hash = RollingHash::with_params([(10, 1_000), (10, 11)]);
+        hash.extend([1, 2, 3]);
+        assert_eq!(hash.hash(), (123, 123 % 11));
+        assert_eq!(hash.len(), 3);
+
+        hash.slide(1, 4);
+        assert_eq!(hash.hash(), (234, 234 % 11));
+        assert_eq!(hash.len(), 3);
+
+        hash.clear();
+        assert!(hash.is_empty());
+        hash.push(5);
+        assert_eq!(hash.hash(), (5, 5));
+    }
+
+    #[test]
+    fn huge_modulus_does_not_overflow() {
+        let mut hash = RollingHash::with_params([(u64::MAX - 1, u64::MAX); 2]);
+        hash.extend([u64::MAX - 2; 10]);
+        hash.slide(u64::MAX - 2, 1);
+
+        let mut expected = RollingHash::with_params([(u64::MAX - 1, u64::MAX); 2]);
+        expected.extend([u64::MAX - 2; 9]);
+        expected.push(1);
+        assert_eq!(hash.hash(), expected.hash());
+    }
+
+    #[test]
+    #[should_panic]
+    fn base_must_be_less_than_modulus() {
+        RollingHash::with_params([(10, 10), (2, 3)]);
+    }
+
+    proptest! {
+        #[test]
+        fn window_hashes_match_direct_hashes(text in prop::collection::vec(any::<u8>(), 0..50), len in 0usize..6) {
+            let expected: Vec<_> = (0..=text.len().saturating_sub(len))
+                .filter_map(|start| text.get(start..start + len))
+                .map(|window| RollingHash::from_bytes(window).hash())
+                .collect();
+
+            prop_assert_eq!(WindowHashes::new(&text, len).collect::<Vec<_>>(), expected);
+        }
+    }
+}
@@ -0,0 +1,377 @@
+//! Suffix array with an LCP array
+//!
+//! Suffixes are sorted by prefix doubling: after a round, suffixes are ordered by their first `2^k` bytes,
+//! and the next round sorts pairs of classes of halves with a counting sort, so building takes O(n log n).
+//! The LCP array is computed from the suffix array by Kasai's algorithm in O(n)
+
+use std::cmp::Ordering;
+
+/// Sorted suffixes of a byte string
+#[derive(Debug, Clone)]
+pub struct SuffixArray<'a> {
+    text: &'a [u8],
+    /// starts of the suffixes in lexicographic order
+    suffixes: Vec<usize>,
+    /// `lcp[i]` is the length of the longest common prefix of the suffixes at `i` and `i + 1`
+    lcp: Vec<usize>,
+}
+
+impl<'a> SuffixArray<'a> {
+    pub fn new(text: &'a [u8]) -> Self {
+        let values: Vec<usize> = text.iter().map(|&byte| byte as usize).collect();
+        let suffixes = sort_suffixes(&values, u8::MAX as usize + 1);
+        let lcp = kasai(text, &suffixes);
+
+        SuffixArray {
+            text,
+            suffixes,
+            lcp,
+        }
+    }
+
+    pub fn text(&self) -> &'a [u8] {
+        self.text
+    }
+
+    pub fn len(&self) -> usize {
+        self.suffixes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.suffixes.is_empty()
+    }
+
+    /// starts of the suffixes in lexicographic order
+    pub fn suffixes(&self) -> &[usize] {
+        &self.suffixes
+    }
+
+    /// lengths of the longest common prefixes of the adjacent suffixes
+    pub fn lcp(&self) -> &[usize] {
+        &self.lcp
+    }
+
+    /// starts of all occurrences of the pattern in lexicographic order of their suffixes.
+    /// The empty pattern is found at every start except the end of the text, which has no suffix
+    pub fn occurrences(&self, pattern: &[u8]) -> &[usize] {
+        let prefix_cmp = |&start: &usize| {
+            let end = self.text.len().min(start + pattern.len());
+            self.text[start..end].cmp(pattern)
+        };
+
+        let from = self
+            .suffixes
+            .partition_point(|start| prefix_cmp(start) == Ordering::Less);
+        let to = from
+            + self.suffixes[from..].partition_point(|start| prefix_cmp(start) == Ordering::Equal);
+
+        &self.suffixes[from..to]
+    }
+
+    /// check if the pattern is a substring of the text
+    pub fn contains(&self, pattern: &[u8]) -> bool {
+        pattern.is_empty() || !self.occurrences(pattern).is_empty()
+    }
+
+    /// number of (possibly overlapping) occurrences of the pattern.
+    /// The empty pattern occurs at every position including the end of the text
+    pub fn count(&self, pattern: &[u8]) -> usize {
+        if pattern.is_empty() {
+            return self.text.len() + 1;
+        }
+
+        self.occurrences(pattern).len()
+    }
+
+    /// the longest substring occurring at least twice, the leftmost one in case of a tie
+    pub fn longest_repeated_substring(&self) -> &'a [u8] {
+        let best = (0..self.lcp.len())
+            .map(|idx| {
+                (
+                    self.lcp[idx],
+                    self.suffixes[idx].min(self.suffixes[idx + 1]),
+                )
+            })
+            .max_by(|(len1, start1), (len2, start2)| len1.cmp(len2).then(start2.cmp(start1)));
+
+        best.map_or(&[], |(len, start)| &self.text[start..start + len])
+    }
+}
+
+/// the longest substring of both strings, the leftmost one in `a` in case of a tie
+pub fn longest_common_substring<'a>(a: &'a [u8], b: &[u8]) -> &'a [u8] {
+    // the separator is unique, so common prefixes never cross it
+    const SEPARATOR: usize = 0;
+    let values: Vec<usize> = a.iter().chain(b).map(|&byte| byte as usize + 1).collect();
+    let values = [&values[..a.len()], &[SEPARATOR], &values[a.len()..]].concat();
+
+    let suffixes = sort_suffixes(&values, u8::MAX as usize + 2);
+    let lcp = kasai(&values, &suffixes);
+
+    // neighbours from different strings share the longest common prefixes
+    let best_len = lcp
+        .iter()
+        .enumerate()
+        .filter(|&(idx, _)| {
+            let (start1, start2) = (suffixes[idx], suffixes[idx + 1]);
+            start1.min(start2) < a.len() && start1.max(start2) > a.len()
+        })
+        .map(|(_, &len)| len)
+        .max()
+        .unwrap_or(0);
+
+    if best_len == 0 {
+        return &a[..0];
+    }
+
+    // suffixes starting with the same substring of `best_len` values form a range,
+    // so the leftmost occurrence in `a` is the smallest start of `a` in a range with a suffix of `b`
+    let mut best_start = a.len();
+    let mut range_start = 0;
+    for range_end in 1..=suffixes.len() {
+        if range_end < suffixes.len() && lcp[range_end - 1] >= best_len {
+            continue;
+        }
+
+        let range = &suffixes[range_start..range_end];
+        if range.iter().any(|&start| start > a.len()) {
+            let start_in_a = range.iter().copied().filter(|&start| start < a.len()).min();
+            best_start = best_start.min(start_in_a.unwrap_or(a.len()));
+        }
+        range_start = range_end;
+    }
+
+    &a[best_start..best_start + best_len]
+}
+
+/// sort suffixes of a sequence of values less than `alphabet` by prefix doubling.
+///
+/// Suffixes are sorted as rotations of the sequence with a sentinel appended,
+/// which is smaller than every value, so the order of rotations is the order of suffixes
+fn sort_suffixes(values: &[usize], alphabet: usize) -> Vec<usize> {
+    let s: Vec<usize> = values.iter().map(|&value| value + 1).chain([0]).collect();
+    let n = s.len();
+
+    // rotations ordered by their first `len` values and their classes of equal prefixes
+    let mut order = vec![0; n];
+    let mut classes = s.clone();
+    let mut counts = vec![0; (alphabet + 1).max(n)];
+
+    for &value in &s {
+        counts[value] += 1;
+    }
+    counting_sort(&mut order, (0..n).rev(), &classes, &mut counts);
+    let mut classes_count = reclassify(&order, &mut classes, |rotation| s[rotation]);
+
+    let mut len = 1;
+    while len < n && classes_count < n {
+        // rotations ordered by their second halves, since a second half is a rotation itself
+        let by_second_half: Vec<usize> = order
+            .iter()
+            .map(|&rotation| (rotation + n - len) % n)
+            .collect();
+
+        let counts = &mut counts[..classes_count];
+        counts.fill(0);
+        for &rotation in &by_second_half {
+            counts[classes[rotation]] += 1;
+        }
+        counting_sort(
+            &mut order,
+            by_second_half.into_iter().rev(),
+            &classes,
+            counts,
+        );
+
+        let halves = classes.clone();
+        classes_count = reclassify(&order, &mut classes, |rotation| {
+            (halves[rotation], halves[(rotation + len) % n])
+        });
+        len *= 2;
+    }
+
+    // the sentinel rotation is the smallest one
+    order.remove(0);
+    order
+}
+
+/// stable counting sort of rotations by their classes, given in reversed order.
+/// `counts` holds numbers of rotations in every class
+fn counting_sort(
+    order: &mut [usize],
+    rotations: impl Iterator<Item = usize>,
+    classes: &[usize],
+    counts: &mut [usize],
+) {
+    for idx in 1..counts.len() {
+        counts[idx] += counts[idx - 1];
+    }
+
+    for rotation in rotations {
+        counts[classes[rotation]] -= 1;
+        order[counts[classes[rotation]]] = rotation;
+    }
+}
+
+/// assign equal classes to adjacent sorted rotations with equal keys and return the number of classes
+fn reclassify<K: PartialEq>(
+    order: &[usize],
+    classes: &mut [usize],
+    key: impl Fn(usize) -> K,
+) -> usize {
+    let mut class = 0;
+    classes[order[0]] = 0;
+
+    for pair in order.windows(2) {
+        if key(pair[0]) != key(pair[1]) {
+            class += 1;
+        }
+        classes[pair[1]] = class;
+    }
+
+    class + 1
+}
+
+/// Kasai's algorithm: the common prefix of a suffix and its successor in the suffix array
+/// is shorter by at most one than the one of the previous suffix of the text
+fn kasai<T: PartialEq>(s: &[T], suffixes: &[usize]) -> Vec<usize> {
+    let mut rank = vec![0; s.len()];
+    for (idx, &start) in suffixes.iter().enumerate() {
+        rank[start] = idx;
+    }
+
+    let mut lcp = vec![0; s.len().saturating_sub(1)];
+    let mut len: usize = 0;
+    for start in 0..s.len() {
+        let Some(&next) = suffixes.get(rank[start] + 1) else {
+            len = 0;
+            continue;
+        };
+
+        while start + len < s.len() && next + len < s.len() && s[start + len] == s[next + len] {
+            len += 1;
+        }
+        lcp[rank[start]] = len;
+        len = len.saturating_sub(1);
+    }
+
+    lcp
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+    use rstest::{fixture, rstest};
+
+    use super::*;
+
+    #[fixture]
+    fn banana() -> SuffixArray<'static> {
+        SuffixArray::new(b"banana")
+    }
+
+    #[rstest]
+    fn suffixes_are_ordered(banana: SuffixArray<'static>) {
+        assert_eq!(banana.suffixes(), [5, 3, 1, 0, 4, 2]);
+        assert_eq!(banana.lcp(), [1, 3, 0, 0, 2]);
+    }
+
+    #[rstest]
+    #[case(b"ana", vec![1, 3])]
+    #[case(b"na", vec![2, 4])]
+    #[case(b"banana", vec![0])]
+    #[case(b"bananas", vec![])]
+    #[case(b"x", vec![])]
+    fn finds_occurrences(
+        banana: SuffixArray<'static>,
+        #[case] pattern: &[u8],
+        #[case] expected: Vec<usize>,
+    ) {
+        let mut found = banana.occurrences(pattern).to_vec();
+        found.sort_unstable();
+
+        assert_eq!(found, expected);
+        assert_eq!(banana.count(pattern), expected.len());
+        assert_eq!(banana.contains(pattern), !expected.is_empty());
+    }
+
+    #[rstest]
+    #[case(b"banana", 7)]
+    #[case(b"", 1)]
+    fn counts_empty_pattern_at_every_position(#[case] text: &[u8], #[case] expected: usize) {
+        let suffix_array = SuffixArray::new(text);
+
+        assert_eq!(suffix_array.count(b""), expected);
+        assert!(suffix_array.contains(b""));
+    }
+
+    #[rstest]
+    #[case(b"banana", b"ana")]
+    #[case(b"abcd", b"")]
+    #[case(b"", b"")]
+    #[case(b"aaaa", b"aaa")]
+    #[case(b"abcabxab", b"ab")]
+    fn finds_longest_repeated_substring(#[case] text: &[u8], #[case] expected: &[u8]) {
+        assert_eq!(
+            SuffixArray::new(text).longest_repeated_substring(),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case(b"xabcdy", b"zbcdabq", b"bcd")]
+    #[case(b"abc", b"def", b"")]
+    #[case(b"", b"abc", b"")]
+    #[case(b"abab", b"baba", b"aba")]
+    fn finds_longest_common_substring(#[case] a: &[u8], #[case] b: &[u8], #[case] expected: &[u8]) {
+        assert_eq!(longest_common_substring(a, b), expected);
+    }
+
+    #[rstest]
+    #[case(b"aa", b"a", 0)]
+    #[case(b"bb", b"b", 0)]
+    #[case(b"aa", b"ba", 0)]
+    #[case(b"xabab", b"bab", 2)]
+    fn finds_leftmost_longest_common_substring(
+        #[case] a: &[u8],
+        #[case] b: &[u8],
+        #[case] expected_start: usize,
+    ) {
+        let found = longest_common_substring(a, b);
+        assert!(std::ptr::eq(
+            found,
+            &a[expected_start..expected_start + found.len()]
+        ));
+    }
+
+    proptest! {
+        #[test]
+        fn matches_naive_sort(text in prop::collection::vec(0u8..3, 0..40)) {
+            let mut expected: Vec<usize> = (0..text.len()).collect();
+            expected.sort_by_key(|&start| &text[start..]);
+
+            let suffix_array = SuffixArray::new(&text);
+            prop_assert_eq!(suffix_array.suffixes(), &expected[..]);
+
+            let expected_lcp: Vec<usize> = expected
+                .windows(2)
+                .map(|pair| text[pair[0]..].iter().zip(&text[pair[1]..]).take_while(|(a, b)| a == b).count())
+                .collect();
+            prop_assert_eq!(suffix_array.lcp(), &expected_lcp[..]);
+        }
+
+        #[test]
+        fn longest_common_substring_matches_naive_search(a in "[ab]{0,12}", b in "[ab]{0,12}") {
+            // the leftmost of the longest common substrings
+            let expected = (0..=a.len())
+                .flat_map(|start| (start..=a.len()).map(move |end| (start, end)))
+                .filter(|&(start, end)| b.contains(&a[start..end]))
+                .max_by(|(start1, end1), (start2, end2)| (end1 - start1).cmp(&(end2 - start2)).then(start2.cmp(start1)))
+                .unwrap_or((0, 0));
+
+            let found = longest_common_substring(a.as_bytes(), b.as_bytes());
+            prop_assert!(std::ptr::eq(found, &a.as_bytes()[expected.0..expected.1]));
+            prop_assert!(b.contains(std::str::from_utf8(found).unwrap()));
+        }
+    }
+}
@@ -0,0 +1,322 @@
+//! Suffix automaton
+//!
+//! The smallest DFA accepting all suffixes of a text. Every state is a class of substrings
+//! with the same set of end positions, so every substring is a path from the root
+//! and substring queries take time linear in the query. For texts of `n >= 2` bytes
+//! the automaton has at most `2n - 1` states and is built online in amortized O(1) per appended byte
+
+use std::{collections::BTreeMap, io::Write};
+
+const ROOT: usize = 0;
+
+#[derive(Debug, Clone)]
+struct State {
+    /// length of the longest substring of the state
+    len: usize,
+    /// state of the longest suffix which ends at more positions, `None` for the root
+    link: Option<usize>,
+    next: BTreeMap<u8, usize>,
+    /// position of the last byte of the first occurrence of the state's substrings
+    first_end: usize,
+}
+
+/// Automaton of all substrings of a text which can be extended byte by byte
+#[derive(Debug, Clone)]
+pub struct SuffixAutomaton {
+    states: Vec<State>,
+    /// state of the whole text
+    last: usize,
+    distinct_substrings: usize,
+}
+
+impl SuffixAutomaton {
+    pub fn new() -> Self {
+        SuffixAutomaton {
+            states: vec![State {
+                len: 0,
+                link: None,
+                next: BTreeMap::new(),
+                first_end: 0,
+            }],
+            last: ROOT,
+            distinct_substrings: 0,
+        }
+    }
+
+    /// length of the text
+    pub fn len(&self) -> usize {
+        self.states[self.last].len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn states_count(&self) -> usize {
+        self.states.len()
+    }
+
+    /// append a byte to the text
+    pub fn push(&mut self, byte: u8) {
+        let len = self.len() + 1;
+        let cur = self.add_state(len, len - 1);
+
+        let mut state = Some(self.last);
+        while let Some(prev) = state.filter(|&prev| !self.states[prev].next.contains_key(&byte)) {
+            self.states[prev].next.insert(byte, cur);
+            state = self.states[prev].link;
+        }
+
+        self.states[cur].link = Some(match state {
+            None => ROOT,
+            Some(prev) => {
+                let next = self.states[prev].next[&byte];
+
+                if self.states[prev].len + 1 == self.states[next].len {
+                    next
+                } else {
+                    self.split(prev, next, byte)
+                }
+            }
+        });
+
+        let link_len = self.states[self.states[cur].link.unwrap()].len;
+        self.distinct_substrings += len - link_len;
+        self.last = cur;
+    }
+
+    /// check if the pattern is a substring of the text
+    pub fn contains(&self, pattern: &[u8]) -> bool {
+        self.walk(pattern).is_some()
+    }
+
+    /// start of the first occurrence of the pattern
+    pub fn first_occurrence(&self, pattern: &[u8]) -> Option<usize> {
+        if pattern.is_empty() {
+            return Some(0);
+        }
+
+        let state = self.walk(pattern)?;
+        Some(self.states[state].first_end + 1 - pattern.len())
+    }
+
+    /// number of different non-empty substrings of the text
+    pub fn distinct_substrings(&self) -> usize {
+        self.distinct_substrings
+    }
+
+    /// Write the automaton in Graphviz DOT format.
+    /// States accepting suffixes of the text are double circles, suffix links are dashed
+    pub fn generate_dot<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        writeln!(writer, "digraph SuffixAutomaton {{")?;
+        writeln!(writer, "  rankdir=LR;")?;
+
+        let mut terminal = vec![false; self.states.len()];
+        let mut state = Some(self.last);
+        while let Some(idx) = state {
+            terminal[idx] = true;
+            state = self.states[idx].link;
+        }
+
+        for (idx, is_terminal) in terminal.into_iter().enumerate() {
+            let shape = if is_terminal {
+                "doublecircle"
+            } else {
+                "circle"
+            };
+            writeln!(writer, "  N{}[label=\"{}\"; shape={}];", idx, idx, shape)?;
+        }
+
+        for (idx, state) in self.states.iter().enumerate() {
+            for (&byte, &next) in &state.next {
+                let label = (byte as char).escape_default();
+                writeln!(writer, "  N{} -> N{} [label=\"{}\"];", idx, next, label)?;
+            }
+        }
+
+        for (idx, state) in self.states.iter().enumerate() {
+            if let Some(link) = state.link {
+                writeln!(
+                    writer,
+                    "  N{} -> N{} [style=dashed; color=\"blue\"];",
+                    idx, link
+                )?;
+            }
+        }
+
+        writeln!(writer, "}}")?;
+
+        Ok(())
+    }
+}
+
+// private
+impl SuffixAutomaton {
+    fn add_state(&mut self, len: usize, first_end: usize) -> usize {
+        self.states.push(State {
+            len,
+            link: None,
+            next: BTreeMap::new(),
+            first_end,
+        });
+
+        self.states.len() - 1
+    }
+
+    /// clone `next` into a state with the substrings no longer than `prev`'s ones plus the byte,
+    /// and redirect the transitions by the byte from `prev` and its suffixes to the clone
+    fn split(&mut self, prev: usize, next: usize, byte: u8) -> usize {
+        let clone = self.add_state(self.states[prev].len + 1, self.states[next].first_end);
+        self.states[clone].next = self.states[next].next.clone();
+        self.states[clone].link = self.states[next].link;
+        self.states[next].link = Some(clone);
+
+        let mut state = Some(prev);
+        while let Some(idx) = state.filter(|&idx| self.states[idx].next.get(&byte) == Some(&next)) {
+            self.states[idx].next.insert(byte, clone);
+            state = self.states[idx].link;
+        }
+
+        clone
+    }
+
+    /// state reached by the pattern from the root
+    fn walk(&self, pattern: &[u8]) -> Option<usize> {
+        pattern.iter().try_fold(ROOT, |state, byte| {
+            self.states[state].next.get(byte).copied()
+        })
+    }
+}
+
+impl Default for SuffixAutomaton {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Extend<u8> for SuffixAutomaton {
+    fn extend<I: IntoIterator<Item = u8>>(&mut self, iter: I) {
+        for byte in iter {
+            self.push(byte);
+        }
+    }
+}
+
+impl FromIterator<u8> for SuffixAutomaton {
+    fn from_iter<I: IntoIterator<Item = u8>>(iter: I) -> Self {
+        let mut automaton = Self::new();
+        automaton.extend(iter);
+
+        automaton
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use proptest::prelude::*;
+    use rstest::{fixture, rstest};
+
+    use super::*;
+
+    #[fixture]
+    fn abcbc() -> SuffixAutomaton {
+        b"abcbc".iter().copied().collect()
+    }
+
+    #[rstest]
+    #[case(b"", Some(0))]
+    #[case(b"abcbc", Some(0))]
+    #[case(b"bc", Some(1))]
+    #[case(b"cb", Some(2))]
+    #[case(b"c", Some(2))]
+    #[case(b"ac", None)]
+    #[case(b"abcbcb", None)]
+    fn finds_first_occurrence(
+        abcbc: SuffixAutomaton,
+        #[case] pattern: &[u8],
+        #[case] expected: Option<usize>,
+    ) {
+        assert_eq!(abcbc.first_occurrence(pattern), expected);
+        assert_eq!(abcbc.contains(pattern), expected.is_some());
+    }
+
+    #[rstest]
+    fn counts_distinct_substrings(mut abcbc: SuffixAutomaton) {
+        // a, b, c, ab, bc, cb, abc, bcb, cbc, abcb, bcbc, abcbc
+        assert_eq!(abcbc.distinct_substrings(), 12);
+        assert_eq!(abcbc.len(), 5);
+
+        abcbc.push(b'a');
+        assert_eq!(abcbc.distinct_substrings(), 17);
+        assert_eq!(abcbc.first_occurrence(b"ca"), Some(4));
+    }
+
+    #[test]
+    fn handles_single_byte_text() {
+        let automaton: SuffixAutomaton = b"a".iter().copied().collect();
+
+        assert_eq!(automaton.states_count(), 2);
+        assert_eq!(automaton.distinct_substrings(), 1);
+        assert_eq!(automaton.first_occurrence(b""), Some(0));
+        assert_eq!(automaton.first_occurrence(b"a"), Some(0));
+    }
+
+    #[test]
+    fn generates_dot() {
+        let automaton: SuffixAutomaton = b"aa".iter().copied().collect();
+
+        let mut buffer = vec![];
+        automaton.generate_dot(&mut buffer).unwrap();
+
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "digraph SuffixAutomaton {\n  rankdir=LR;\n  \
+             N0[label=\"0\"; shape=doublecircle];\n  \
+             N1[label=\"1\"; shape=doublecircle];\n  \
+             N2[label=\"2\"; shape=doublecircle];\n  \
+             N0 -> N1 [label=\"a\"];\n  \
+             N1 -> N2 [label=\"a\"];\n  \
+             N1 -> N0 [style=dashed; color=\"blue\"];\n  \
+             N2 -> N1 [style=dashed; color=\"blue\"];\n}\n"
+        );
+    }
+
+    #[test]
+    fn escapes_dot_labels() {
+        let automaton: SuffixAutomaton = b"a\"".iter().copied().collect();
+
+        let mut buffer = vec![];
+        automaton.generate_dot(&mut buffer).unwrap();
+
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "digraph SuffixAutomaton {\n  rankdir=LR;\n  \
+             N0[label=\"0\"; shape=doublecircle];\n  \
+             N1[label=\"1\"; shape=circle];\n  \
+             N2[label=\"2\"; shape=doublecircle];\n  \
+             N0 -> N2 [label=\"\\\"\"];\n  \
+             N0 -> N1 [label=\"a\"];\n  \
+             N1 -> N2 [label=\"\\\"\"];\n  \
+             N1 -> N0 [style=dashed; color=\"blue\"];\n  \
+             N2 -> N0 [style=dashed; color=\"blue\"];\n}\n"
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn matches_naive_queries(text in "[abc]{0,20}", pattern in "[abc]{0,4}") {
+            let automaton: SuffixAutomaton = text.bytes().collect();
+
+            let substrings: HashSet<&str> = (0..text.len())
+                .flat_map(|start| (start + 1..=text.len()).map(move |end| (start, end)))
+                .map(|(start, end)| &text[start..end])
+                .collect();
+            prop_assert_eq!(automaton.distinct_substrings(), substrings.len());
+            prop_assert!(automaton.states_count() <= (2 * text.len()).saturating_sub(1).max(text.len() + 1));
+
+            prop_assert_eq!(automaton.first_occurrence(pattern.as_bytes()), text.find(&pattern));
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use std::cmp::Ordering;
+
+use super::Searcher;
+
+/// Crochemore–Perrin two-way searcher.
+///
+/// The pattern is split at a critical factorization `u v`. Every window is compared
+/// with `v` from the left to the right and then with `u` from the right to the left,
+/// so the search takes O(n + m) time with only constant extra memory
+#[derive(Debug, Clone)]
+pub struct TwoWay {
+    pattern: Vec<u8>,
+    /// index of the last byte of `u`, -1 when `u` is empty
+    critical: isize,
+    /// shift after a full match
+    period: usize,
+    /// `u` is a suffix of `v`'s prefix of the pattern's period,
+    /// so the matched part can be remembered after shifting by the period
+    periodic: bool,
+}
+
+impl TwoWay {
+    pub fn new(pattern: impl AsRef<[u8]>) -> Self {
+        let pattern = pattern.as_ref().to_vec();
+        let m = pattern.len();
+
+        let (suffix, suffix_period) = maximal_suffix(&pattern, Ordering::Less);
+        let (suffix_rev, suffix_rev_period) = maximal_suffix(&pattern, Ordering::Greater);
+        let (critical, period) = if suffix > suffix_rev {
+            (suffix, suffix_period)
+        } else {
+            (suffix_rev, suffix_rev_period)
+        };
+
+        let u_len = (critical + 1) as usize;
+        let periodic = pattern.get(period..period + u_len) == Some(&pattern[..u_len]);
+        let period = if periodic {
+            period
+        } else {
+            u_len.max(m - u_len) + 1
+        };
+
+        TwoWay {
+            pattern,
+            critical,
+            period,
+            periodic,
+        }
+    }
+
+    /// the critical factorization of the pattern into `u` and `v`
+    pub fn factorization(&self) -> (&[u8], &[u8]) {
+        self.pattern.split_at((self.critical + 1) as usize)
+    }
+}
+
+/// maximal suffix of a string with respect to a byte order:
+/// start of the suffix minus one, and the period of the suffix.
+/// `Ordering::Less` gives the maximal suffix for the usual order, `Ordering::Greater` for the reversed one
+fn maximal_suffix(s: &[u8], smaller: Ordering) -> (isize, usize) {
+    let (mut suffix, mut j, mut k, mut period) = (-1isize, 0, 1, 1);
+
+    while j + k < s.len() {
+        let a = s[j + k];
+        let b = s[(suffix + k as isize) as usize];
+
+        match a.cmp(&b) {
+            ordering if ordering == smaller => {
+                j += k;
+                k = 1;
+                period = (j as isize - suffix) as usize;
+            }
+            Ordering::Equal if k != period => k += 1,
+            Ordering::Equal => {
+                j += period;
+                k = 1;
+            }
+            _ => {
+                suffix = j as isize;
+                j += 1;
+                k = 1;
+                period = 1;
+            }
+        }
+    }
+
+    (suffix, period)
+}
+
+impl Searcher for TwoWay {
+    fn pattern(&self) -> &[u8] {
+        &self.pattern
+    }
+
+    fn find_from(&self, text: &[u8], start: usize) -> Option<usize> {
+        if start > text.len() {
+            return None;
+        }
+
+        let mut iter = TwoWayIter {
+            searcher: self,
+            text,
+            pos: start,
+            memory: -1,
+        };
+
+        iter.next()
+    }
+
+    fn find_iter<'a>(&'a self, text: &'a [u8]) -> impl Iterator<Item = usize> + 'a {
+        TwoWayIter {
+            searcher: self,
+            text,
+            pos: 0,
+            memory: -1,
+        }
+    }
+}
+
+/// Iterator over occurrences keeping the matched prefix between them
+#[derive(Debug, Clone)]
+struct TwoWayIter<'a> {
+    searcher: &'a TwoWay,
+    text: &'a [u8],
+    /// next window to check
+    pos: usize,
+    /// the window's bytes up to this index are known to match the pattern
+    memory: isize,
+}
+
+impl Iterator for TwoWayIter<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let TwoWay {
+            pattern,
+            critical,
+            period,
+            periodic,
+        } = self.searcher;
+        let m = pattern.len();
+
+        while self.pos + m <= self.text.len() {
+            let window = &self.text[self.pos..self.pos + m];
+            let pos = self.pos;
+
+            if m == 0 {
+                self.pos += 1;
+                return Some(pos);
+            }
+
+            // `u` bytes before this index are known to match
+            let known = if *periodic { self.memory } else { -1 };
+
+            // match `v` from the left to the right
+            let mut i = ((*critical).max(known) + 1) as usize;
+            while i < m && pattern[i] == window[i] {
+                i += 1;
+            }
+            if i < m {
+                self.pos += (i as isize - critical) as usize;
+                self.memory = -1;
+                continue;
+            }
+
+            // match `u` from the right to the left
+            let mut i = *critical;
+            while i > known && pattern[i as usize] == window[i as usize] {
+                i -= 1;
+            }
+
+            self.pos += period;
+            if *periodic {
+                self.memory = m as isize - *period as isize - 1;
+            }
+            if i <= known {
+                return Some(pos);
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(b"a", b"", b"a")]
+    #[case(b"banana", b"ba", b"nana")]
+    #[case(b"abaab", b"ab", b"aab")]
+    #[case(b"zzzaz", b"zzz", b"az")]
+    fn finds_critical_factorization(#[case] pattern: &[u8], #[case] u: &[u8], #[case] v: &[u8]) {
+        assert_eq!(TwoWay::new(pattern).factorization(), (u, v));
+    }
+
+    #[rstest]
+    #[case("aaa", "aaaaaa", vec![0, 1, 2, 3])]
+    #[case("abab", "abababcabab", vec![0, 2, 7])]
+    #[case("aab", "aaaabaab", vec![2, 5])]
+    #[case("banana", "bananananabanana", vec![0, 10])]
+    fn finds_periodic_patterns(
+        #[case] pattern: &str,
+        #[case] text: &str,
+        #[case] expected: Vec<usize>,
+    ) {
+        let searcher = TwoWay::new(pattern);
+
+        assert_eq!(
+            searcher.find_iter(text.as_bytes()).collect::<Vec<_>>(),
+            expected
+        );
+    }
+}
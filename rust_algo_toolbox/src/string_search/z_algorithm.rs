@@ -0,0 +1,168 @@
+use super::Searcher;
+
+/// Z-function of a string: for every position, the length of the longest common prefix
+/// of the string and its suffix starting there. The value at position 0 is the string's length
+pub fn z_function(s: &[u8]) -> Vec<usize> {
+    let mut result = vec![0; s.len()];
+    if s.is_empty() {
+        return result;
+    }
+    result[0] = s.len();
+
+    // `s[left..right]` is the rightmost found segment which matches a prefix
+    let (mut left, mut right) = (0, 0);
+    for i in 1..s.len() {
+        let mut len = if i < right {
+            result[i - left].min(right - i)
+        } else {
+            0
+        };
+
+        while i + len < s.len() && s[len] == s[i + len] {
+            len += 1;
+        }
+
+        if i + len > right {
+            (left, right) = (i, i + len);
+        }
+        result[i] = len;
+    }
+
+    result
+}
+
+/// Searcher based on the Z-function of the pattern.
+/// Common prefixes of the pattern and suffixes of the text are computed the same way
+/// as the Z-function itself, reusing the rightmost segment of the text matching a prefix
+#[derive(Debug, Clone)]
+pub struct ZAlgorithm {
+    pattern: Vec<u8>,
+    z: Vec<usize>,
+}
+
+impl ZAlgorithm {
+    pub fn new(pattern: impl AsRef<[u8]>) -> Self {
+        let pattern = pattern.as_ref().to_vec();
+
+        ZAlgorithm {
+            z: z_function(&pattern),
+            pattern,
+        }
+    }
+
+    /// Z-function of the pattern
+    pub fn z(&self) -> &[usize] {
+        &self.z
+    }
+}
+
+impl Searcher for ZAlgorithm {
+    fn pattern(&self) -> &[u8] {
+        &self.pattern
+    }
+
+    fn find_from(&self, text: &[u8], start: usize) -> Option<usize> {
+        if start > text.len() {
+            return None;
+        }
+
+        let mut iter = ZIter {
+            searcher: self,
+            text,
+            pos: start,
+            left: start,
+            right: start,
+        };
+
+        iter.next()
+    }
+
+    fn find_iter<'a>(&'a self, text: &'a [u8]) -> impl Iterator<Item = usize> + 'a {
+        ZIter {
+            searcher: self,
+            text,
+            pos: 0,
+            left: 0,
+            right: 0,
+        }
+    }
+}
+
+/// Iterator over occurrences keeping the rightmost matching segment between them
+#[derive(Debug, Clone)]
+struct ZIter<'a> {
+    searcher: &'a ZAlgorithm,
+    text: &'a [u8],
+    /// next position to check
+    pos: usize,
+    /// `text[left..right]` matches a prefix of the pattern
+    left: usize,
+    right: usize,
+}
+
+impl Iterator for ZIter<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pattern = &self.searcher.pattern;
+
+        while self.pos + pattern.len() <= self.text.len() {
+            let i = self.pos;
+            self.pos += 1;
+
+            if pattern.is_empty() {
+                return Some(i);
+            }
+
+            let mut len = if i < self.right {
+                self.searcher.z[i - self.left].min(self.right - i)
+            } else {
+                0
+            };
+
+            while len < pattern.len()
+                && i + len < self.text.len()
+                && pattern[len] == self.text[i + len]
+            {
+                len += 1;
+            }
+
+            if i + len > self.right {
+                (self.left, self.right) = (i, i + len);
+            }
+
+            if len == pattern.len() {
+                return Some(i);
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(b"", vec![])]
+    #[case(b"a", vec![1])]
+    #[case(b"aaaaa", vec![5, 4, 3, 2, 1])]
+    #[case(b"aaabaab", vec![7, 2, 1, 0, 2, 1, 0])]
+    #[case(b"abacaba", vec![7, 0, 1, 0, 3, 0, 1])]
+    fn z_function_works(#[case] s: &[u8], #[case] expected: Vec<usize>) {
+        assert_eq!(z_function(s), expected);
+    }
+
+    #[test]
+    fn find_from_skips_earlier_matches() {
+        let searcher = ZAlgorithm::new("aba");
+
+        assert_eq!(searcher.find_from(b"ababa", 0), Some(0));
+        assert_eq!(searcher.find_from(b"ababa", 1), Some(2));
+        assert_eq!(searcher.find_from(b"ababa", 3), None);
+        assert_eq!(searcher.find_from(b"ababa", 10), None);
+    }
+}
@@ -1,5 +1,6 @@
 pub mod hash_trie;
 pub mod ternary_trie;
+pub mod trie_map;
 
 pub trait Trie {
     type Char;
@@ -21,6 +22,133 @@ pub trait Trie {
     fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Remove a word.
+    /// Returns false if the word doesn't exist
+    fn remove(&mut self, word: impl Iterator<Item = Self::Char>) -> bool;
+
+    /// Number of words starting with a prefix
+    fn count_with_prefix(&self, prefix: impl Iterator<Item = Self::Char>) -> usize;
+
+    /// Find the longest word in the trie which is a prefix of a given word
+    fn longest_prefix_of(&self, word: impl Iterator<Item = Self::Char>) -> Option<Vec<Self::Char>>;
+}
+
+/// Trie with ordered chars, which can enumerate its words
+pub trait OrderedTrie: Trie {
+    /// Iterate over all words in lexicographic order
+    fn iter(&self) -> impl Iterator<Item = Vec<Self::Char>> + '_;
+
+    /// Iterate over words starting with a prefix in lexicographic order
+    fn iter_prefix(
+        &self,
+        prefix: impl Iterator<Item = Self::Char>,
+    ) -> impl Iterator<Item = Vec<Self::Char>> + '_;
+}
+
+/// Map with sequences of chars as keys
+pub trait PrefixMap {
+    type Char;
+    type Value;
+
+    /// Insert a value for a key.
+    /// Returns the previous value of the key
+    fn insert(
+        &mut self,
+        key: impl Iterator<Item = Self::Char>,
+        value: Self::Value,
+    ) -> Option<Self::Value>;
+
+    /// Value of a key
+    fn get(&self, key: impl Iterator<Item = Self::Char>) -> Option<&Self::Value>;
+
+    /// Mutable reference to the value of a key
+    fn get_mut(&mut self, key: impl Iterator<Item = Self::Char>) -> Option<&mut Self::Value>;
+
+    /// Remove a key and return its value
+    fn remove(&mut self, key: impl Iterator<Item = Self::Char>) -> Option<Self::Value>;
+
+    /// Check if a key is in the map
+    fn contains_key(&self, key: impl Iterator<Item = Self::Char>) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Number of keys in the map
+    fn len(&self) -> usize;
+
+    /// Check if the map is empty
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of keys starting with a prefix
+    fn count_with_prefix(&self, prefix: impl Iterator<Item = Self::Char>) -> usize;
+
+    /// Iterate over all keys with their values in lexicographic order of keys
+    fn iter(&self) -> impl Iterator<Item = (Vec<Self::Char>, &Self::Value)> + '_;
+
+    /// Iterate over keys starting with a prefix with their values in lexicographic order of keys
+    fn iter_prefix(
+        &self,
+        prefix: impl Iterator<Item = Self::Char>,
+    ) -> impl Iterator<Item = (Vec<Self::Char>, &Self::Value)> + '_;
+
+    /// Entry of a key for in-place manipulation
+    fn entry(&mut self, key: impl Iterator<Item = Self::Char>) -> Entry<'_, Self>
+    where
+        Self: Sized,
+    {
+        Entry {
+            map: self,
+            key: key.collect(),
+        }
+    }
+}
+
+/// A key of a [`PrefixMap`] which may be vacant or occupied
+pub struct Entry<'a, Map: PrefixMap> {
+    map: &'a mut Map,
+    key: Vec<Map::Char>,
+}
+
+impl<'a, Map> Entry<'a, Map>
+where
+    Map: PrefixMap,
+    Map::Char: Clone,
+{
+    pub fn key(&self) -> &[Map::Char] {
+        &self.key
+    }
+
+    /// modify the value if the key is occupied
+    pub fn and_modify(self, f: impl FnOnce(&mut Map::Value)) -> Self {
+        if let Some(value) = self.map.get_mut(self.key.iter().cloned()) {
+            f(value);
+        }
+        self
+    }
+
+    /// insert a value if the key is vacant and return a mutable reference to the key's value
+    pub fn or_insert(self, default: Map::Value) -> &'a mut Map::Value {
+        self.or_insert_with(|| default)
+    }
+
+    /// insert a computed value if the key is vacant and return a mutable reference to the key's value
+    pub fn or_insert_with(self, default: impl FnOnce() -> Map::Value) -> &'a mut Map::Value {
+        if !self.map.contains_key(self.key.iter().cloned()) {
+            self.map.insert(self.key.iter().cloned(), default());
+        }
+
+        self.map.get_mut(self.key.into_iter()).unwrap()
+    }
+
+    /// insert the default value if the key is vacant and return a mutable reference to the key's value
+    pub fn or_default(self) -> &'a mut Map::Value
+    where
+        Map::Value: Default,
+    {
+        self.or_insert_with(Default::default)
+    }
 }
 
 pub trait StringSet {
@@ -35,11 +163,24 @@ pub trait StringSet {
     fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    fn remove(&mut self, word: &str) -> bool;
+
+    fn count_with_prefix(&self, prefix: &str) -> usize;
+
+    /// all words in lexicographic order
+    fn words(&self) -> Vec<String>;
+
+    /// words starting with a prefix in lexicographic order
+    fn words_with_prefix(&self, prefix: &str) -> Vec<String>;
+
+    /// the longest word in the set which is a prefix of a given word
+    fn longest_prefix_of<'a>(&self, word: &'a str) -> Option<&'a str>;
 }
 
 impl<TrieImpl> StringSet for TrieImpl
 where
-    TrieImpl: Trie<Char = char>,
+    TrieImpl: OrderedTrie<Char = char>,
 {
     fn insert(&mut self, word: &str) -> bool {
         self.insert(word.chars())
@@ -56,6 +197,104 @@ where
     fn len(&self) -> usize {
         self.len()
     }
+
+    fn remove(&mut self, word: &str) -> bool {
+        self.remove(word.chars())
+    }
+
+    fn count_with_prefix(&self, prefix: &str) -> usize {
+        self.count_with_prefix(prefix.chars())
+    }
+
+    fn words(&self) -> Vec<String> {
+        self.iter().map(String::from_iter).collect()
+    }
+
+    fn words_with_prefix(&self, prefix: &str) -> Vec<String> {
+        self.iter_prefix(prefix.chars())
+            .map(String::from_iter)
+            .collect()
+    }
+
+    fn longest_prefix_of<'a>(&self, word: &'a str) -> Option<&'a str> {
+        let prefix = self.longest_prefix_of(word.chars())?;
+        let bytes_len = prefix.iter().map(|c| c.len_utf8()).sum();
+
+        Some(&word[..bytes_len])
+    }
+}
+
+pub trait StringMap {
+    type Value;
+
+    fn insert(&mut self, key: &str, value: Self::Value) -> Option<Self::Value>;
+
+    fn get(&self, key: &str) -> Option<&Self::Value>;
+
+    fn get_mut(&mut self, key: &str) -> Option<&mut Self::Value>;
+
+    fn remove(&mut self, key: &str) -> Option<Self::Value>;
+
+    fn contains_key(&self, key: &str) -> bool;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn count_with_prefix(&self, prefix: &str) -> usize;
+
+    /// keys starting with a prefix with their values in lexicographic order of keys
+    fn iter_prefix(&self, prefix: &str) -> impl Iterator<Item = (String, &Self::Value)> + '_;
+
+    fn entry(&mut self, key: &str) -> Entry<'_, Self>
+    where
+        Self: PrefixMap + Sized;
+}
+
+impl<MapImpl> StringMap for MapImpl
+where
+    MapImpl: PrefixMap<Char = char>,
+{
+    type Value = MapImpl::Value;
+
+    fn insert(&mut self, key: &str, value: Self::Value) -> Option<Self::Value> {
+        self.insert(key.chars(), value)
+    }
+
+    fn get(&self, key: &str) -> Option<&Self::Value> {
+        self.get(key.chars())
+    }
+
+    fn get_mut(&mut self, key: &str) -> Option<&mut Self::Value> {
+        self.get_mut(key.chars())
+    }
+
+    fn remove(&mut self, key: &str) -> Option<Self::Value> {
+        self.remove(key.chars())
+    }
+
+    fn contains_key(&self, key: &str) -> bool {
+        self.contains_key(key.chars())
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn count_with_prefix(&self, prefix: &str) -> usize {
+        self.count_with_prefix(prefix.chars())
+    }
+
+    fn iter_prefix(&self, prefix: &str) -> impl Iterator<Item = (String, &Self::Value)> + '_ {
+        self.iter_prefix(prefix.chars())
+            .map(|(key, value)| (String::from_iter(key), value))
+    }
+
+    fn entry(&mut self, key: &str) -> Entry<'_, Self> {
+        self.entry(key.chars())
+    }
 }
 
 #[cfg(test)]
@@ -64,14 +303,14 @@ mod test {
     use rstest::{fixture, rstest};
 
     use lazy_static::lazy_static;
-    use proptest::proptest;
+    use proptest::prelude::*;
     use rand::{
         self,
         distributions::Alphanumeric,
         distributions::{DistString, Uniform},
         prelude::Distribution,
     };
-    use std::fmt::Debug;
+    use std::{collections::BTreeSet, fmt::Debug};
 
     use super::{hash_trie::HashTrie, ternary_trie::TernaryTrie, StringSet};
 
@@ -155,7 +394,7 @@ mod test {
 
     #[fixture]
     fn top100ternary_trie(top100words: Vec<&str>) -> DynStringSet {
-        let mut top100trie = HashTrie::default();
+        let mut top100trie = TernaryTrie::default();
         for word in top100words {
             top100trie.insert(word);
         }
@@ -164,7 +403,7 @@ mod test {
 
     #[fixture]
     fn random_ternary_trie(random_words: Vec<&str>) -> DynStringSet {
-        let mut random_trie = HashTrie::default();
+        let mut random_trie = TernaryTrie::default();
         for word in random_words {
             random_trie.insert(word);
         }
@@ -173,7 +412,7 @@ mod test {
 
     #[fixture]
     fn lol_kek_chebureck_ternary_trie(lol_kek_chebureck_list: Vec<&str>) -> DynStringSet {
-        let mut lol_kek_chebureck_trie = HashTrie::default();
+        let mut lol_kek_chebureck_trie = TernaryTrie::default();
         for word in lol_kek_chebureck_list {
             lol_kek_chebureck_trie.insert(word);
         }
@@ -282,7 +521,145 @@ mod test {
         }
     }
 
+    #[rstest]
+    #[case(lol_kek_chebureck_hash_trie(lol_kek_chebureck_list()))]
+    #[case(lol_kek_chebureck_ternary_trie(lol_kek_chebureck_list()))]
+    fn remove_works(#[case] mut trie: DynStringSet) {
+        assert_returns!(true, StringSet::remove, &mut *trie, "kek");
+        assert_returns!(false, StringSet::remove, &mut *trie, "kek");
+        assert_returns!(false, StringSet::remove, &mut *trie, "lo");
+        assert_returns!(2, StringSet::len, &*trie);
+        assert_returns!(false, StringSet::contains_prefix, &*trie, "ke");
+        assert_returns!(true, StringSet::contains, &*trie, "lol");
+    }
+
+    #[rstest]
+    #[case(top100hash_trie(top100words()))]
+    #[case(top100ternary_trie(top100words()))]
+    fn words_are_sorted(#[case] trie: DynStringSet, mut top100words: Vec<&str>) {
+        top100words.sort_unstable();
+        assert_eq!(trie.words(), top100words);
+    }
+
+    #[rstest]
+    #[case(top100hash_trie(top100words()))]
+    #[case(top100ternary_trie(top100words()))]
+    fn words_with_prefix_are_sorted(#[case] trie: DynStringSet, mut top100words: Vec<&str>) {
+        top100words.sort_unstable();
+
+        for prefix in ["", "a", "th", "wh", "zzz"] {
+            let expected: Vec<_> = top100words
+                .iter()
+                .filter(|word| word.starts_with(prefix))
+                .copied()
+                .collect();
+
+            assert_eq!(trie.words_with_prefix(prefix), expected);
+            assert_returns!(expected.len(), StringSet::count_with_prefix, &*trie, prefix);
+        }
+    }
+
+    #[rstest]
+    #[case(lol_kek_chebureck_hash_trie(lol_kek_chebureck_list()))]
+    #[case(lol_kek_chebureck_ternary_trie(lol_kek_chebureck_list()))]
+    fn longest_prefix_of_works(#[case] mut trie: DynStringSet) {
+        trie.insert("lo");
+
+        assert_returns!(
+            Some("lol"),
+            StringSet::longest_prefix_of,
+            &*trie,
+            "lollipop"
+        );
+        assert_returns!(Some("lo"), StringSet::longest_prefix_of, &*trie, "lost");
+        assert_returns!(Some("kek"), StringSet::longest_prefix_of, &*trie, "kek");
+        assert_returns!(None::<&str>, StringSet::longest_prefix_of, &*trie, "ke");
+        assert_returns!(None::<&str>, StringSet::longest_prefix_of, &*trie, "");
+
+        trie.insert("");
+        assert_returns!(Some(""), StringSet::longest_prefix_of, &*trie, "ke");
+    }
+
+    /// char type which can be hashed but not ordered
+    #[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+    struct Unordered(u8);
+
+    #[test]
+    fn hash_trie_accepts_unordered_chars() {
+        let mut trie = HashTrie::new();
+        for word in [vec![1, 2], vec![1, 3], vec![2]] {
+            super::Trie::insert(&mut trie, word.into_iter().map(Unordered));
+        }
+
+        assert!(super::Trie::contains(
+            &trie,
+            [1, 3].map(Unordered).into_iter()
+        ));
+        assert!(super::Trie::remove(
+            &mut trie,
+            [2].map(Unordered).into_iter()
+        ));
+        assert_eq!(
+            super::Trie::count_with_prefix(&trie, [1].map(Unordered).into_iter()),
+            2
+        );
+    }
+
+    #[test]
+    fn hash_trie_find_all_keeps_last_prefix_char() {
+        let mut trie = HashTrie::new();
+        for word in ["abc", "abd", "b"] {
+            StringSet::insert(&mut trie, word);
+        }
+
+        let mut found: Vec<String> = trie
+            .find_all("ab".chars())
+            .into_iter()
+            .map(String::from_iter)
+            .collect();
+        found.sort();
+
+        assert_eq!(found, ["bc", "bd"]);
+    }
+
+    fn check_against_model(
+        trie: &mut dyn StringSet,
+        operations: &[(bool, String)],
+    ) -> Result<(), proptest::test_runner::TestCaseError> {
+        let mut model = BTreeSet::new();
+
+        for (insert, word) in operations {
+            if *insert {
+                prop_assert_eq!(trie.insert(word), model.insert(word.clone()));
+            } else {
+                prop_assert_eq!(trie.remove(word), model.remove(word));
+            }
+        }
+
+        prop_assert_eq!(trie.len(), model.len());
+        prop_assert_eq!(trie.words(), model.iter().cloned().collect::<Vec<_>>());
+        prop_assert_eq!(trie.count_with_prefix(""), model.len());
+        for prefix in ["a", "b", "ab", "ba"] {
+            let expected = model.iter().filter(|word| word.starts_with(prefix)).count();
+            prop_assert_eq!(trie.count_with_prefix(prefix), expected);
+            // branches without words are pruned on removal
+            prop_assert_eq!(trie.contains_prefix(prefix), expected > 0);
+        }
+
+        Ok(())
+    }
+
     proptest! {
+        #[test]
+        fn hash_trie_matches_model(operations in prop::collection::vec((any::<bool>(), "[abc]{0,4}"), 0..50)) {
+            check_against_model(&mut HashTrie::new(), &operations)?;
+        }
+
+        #[test]
+        fn ternary_trie_matches_model(operations in prop::collection::vec((any::<bool>(), "[abc]{0,4}"), 0..50)) {
+            check_against_model(&mut TernaryTrie::new(), &operations)?;
+        }
+
         #[test]
         fn empty_hash_trie_contains_nothing(ref word in ".*") {
             let empty_trie = HashTrie::new();
@@ -1,10 +1,12 @@
 use std::{collections::HashMap, fmt::Debug, hash::Hash, ops::Deref};
 
-use super::Trie;
+use super::{OrderedTrie, Trie};
 
 pub struct HashTrieNode<Char> {
     character: Char,
     word_end: bool,
+    /// number of words in the subtree
+    words_count: usize,
     children: HashMap<Char, Box<HashTrieNode<Char>>>,
 }
 
@@ -16,6 +18,7 @@ where
         HashTrieNode {
             character,
             word_end,
+            words_count: 0,
             children: HashMap::new(),
         }
     }
@@ -35,36 +38,70 @@ where
     }
 
     fn insert_impl(&mut self, mut word: impl Iterator<Item = Char>) -> bool {
-        if let Some(next_char) = word.next() {
+        let inserted = if let Some(next_char) = word.next() {
             match &mut self.children.get_mut(&next_char) {
-                Some(next_node) => {
-                    return next_node.insert_impl(word);
-                }
+                Some(next_node) => next_node.insert_impl(word),
                 None => {
                     let mut next_node = Box::new(HashTrieNode::new(next_char.clone(), false));
                     let result = next_node.insert_impl(word);
 
                     self.children.insert(next_char, next_node);
-                    return result;
+                    result
                 }
             }
+        } else {
+            !std::mem::replace(&mut self.word_end, true)
+        };
+
+        if inserted {
+            self.words_count += 1;
         }
 
-        if self.word_end {
-            return false;
+        inserted
+    }
+
+    /// remove a word and prune the branches left without words
+    fn remove_impl(&mut self, mut word: impl Iterator<Item = Char>) -> bool {
+        let removed = if let Some(next_char) = word.next() {
+            let Some(next_node) = self.children.get_mut(&next_char) else {
+                return false;
+            };
+
+            let result = next_node.remove_impl(word);
+            if next_node.words_count == 0 {
+                self.children.remove(&next_char);
+            }
+            result
+        } else {
+            std::mem::replace(&mut self.word_end, false)
+        };
+
+        if removed {
+            self.words_count -= 1;
         }
 
-        self.word_end = true;
-        true
+        removed
     }
 
     pub fn word_end(&self) -> bool {
         self.word_end
     }
 
+    /// number of words starting at this node
+    pub fn words_count(&self) -> usize {
+        self.words_count
+    }
+
     pub fn next(&self, character: Char) -> Option<&HashTrieNode<Char>> {
         self.children.get(&character).map(|node| node.deref())
     }
+
+    /// children of the node with the chars leading to them, in arbitrary order
+    pub fn children(&self) -> impl Iterator<Item = (&Char, &HashTrieNode<Char>)> {
+        self.children
+            .iter()
+            .map(|(character, node)| (character, node.deref()))
+    }
 }
 
 impl<Char> HashTrieNode<Char>
@@ -110,6 +147,10 @@ where
         &self.root
     }
 
+    /// Words starting with the prefix in arbitrary order.
+    /// Every word is returned without its prefix except for the last char of the prefix,
+    /// so `"abc"` is found as `"bc"` by the prefix `"ab"`.
+    /// Use [`OrderedTrie::iter_prefix`] to get whole words
     pub fn find_all(&self, prefix: impl Iterator<Item = Char>) -> Vec<Vec<Char>> {
         let mut result = vec![];
         let prefix_node = self.root.find_prefix(prefix);
@@ -143,6 +184,35 @@ where
     }
 }
 
+/// Iterator over words of a [`HashTrie`] in lexicographic order
+pub struct HashTrieWords<'a, Char> {
+    /// nodes to visit with the words leading to them
+    stack: Vec<(&'a HashTrieNode<Char>, Vec<Char>)>,
+}
+
+impl<'a, Char: Clone + Ord> Iterator for HashTrieWords<'a, Char> {
+    type Item = Vec<Char>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((node, word)) = self.stack.pop() {
+            let mut children: Vec<_> = node.children.values().collect();
+            children.sort_unstable_by(|a, b| b.character.cmp(&a.character));
+
+            for child in children {
+                let mut child_word = word.clone();
+                child_word.push(child.character.clone());
+                self.stack.push((child, child_word));
+            }
+
+            if node.word_end {
+                return Some(word);
+            }
+        }
+
+        None
+    }
+}
+
 impl<Char> Trie for HashTrie<Char>
 where
     Char: Eq + Hash + Clone,
@@ -173,6 +243,69 @@ where
     fn len(&self) -> usize {
         self.words_count
     }
+
+    fn remove(&mut self, word: impl Iterator<Item = Self::Char>) -> bool {
+        let removed = self.root.remove_impl(word);
+
+        if removed {
+            self.words_count -= 1;
+        }
+
+        removed
+    }
+
+    fn count_with_prefix(&self, prefix: impl Iterator<Item = Self::Char>) -> usize {
+        self.root
+            .find_prefix(prefix)
+            .map_or(0, |node| node.words_count)
+    }
+
+    fn longest_prefix_of(&self, word: impl Iterator<Item = Self::Char>) -> Option<Vec<Self::Char>> {
+        let mut node = &self.root;
+        let mut consumed = vec![];
+        let mut longest = node.word_end.then_some(0);
+
+        for character in word {
+            let Some(next_node) = node.children.get(&character) else {
+                break;
+            };
+
+            node = next_node;
+            consumed.push(character);
+            if node.word_end {
+                longest = Some(consumed.len());
+            }
+        }
+
+        longest.map(|len| {
+            consumed.truncate(len);
+            consumed
+        })
+    }
+}
+
+impl<Char> OrderedTrie for HashTrie<Char>
+where
+    Char: Eq + Hash + Clone + Ord,
+{
+    fn iter(&self) -> impl Iterator<Item = Vec<Self::Char>> + '_ {
+        self.iter_prefix(std::iter::empty())
+    }
+
+    fn iter_prefix(
+        &self,
+        prefix: impl Iterator<Item = Self::Char>,
+    ) -> impl Iterator<Item = Vec<Self::Char>> + '_ {
+        let prefix: Vec<_> = prefix.collect();
+        let stack = self
+            .root
+            .find_prefix(prefix.iter().cloned())
+            .map(|node| (node, prefix))
+            .into_iter()
+            .collect();
+
+        HashTrieWords { stack }
+    }
 }
 
 impl<Char> Default for HashTrie<Char>
@@ -1,9 +1,11 @@
-use super::Trie;
+use super::{OrderedTrie, Trie};
 use std::{cmp::Ordering, fmt::Debug, iter::Peekable};
 
 pub struct TernaryTrieNode<Char> {
     character: Char,
     word_end: bool,
+    /// number of words in the subtree
+    words_count: usize,
     children: [Option<Box<TernaryTrieNode<Char>>>; 3],
 }
 
@@ -15,6 +17,7 @@ where
         TernaryTrieNode {
             character,
             word_end,
+            words_count: 0,
             children: [None, None, None],
         }
     }
@@ -34,27 +37,65 @@ where
     }
 
     fn insert_impl(&mut self, mut word: Peekable<impl Iterator<Item = Char>>) -> bool {
-        let Some(next_char) = word.peek().cloned() else {
-            if self.word_end {
-                return false;
-            } else {
-                self.word_end = true;
-                return true;
+        let inserted = match word.peek().cloned() {
+            None => !std::mem::replace(&mut self.word_end, true),
+            Some(next_char) => {
+                let child_idx = self.child_idx(&mut word, &next_char);
+
+                self.children[child_idx]
+                    .get_or_insert(Box::new(TernaryTrieNode::new(next_char.clone(), false)))
+                    .insert_impl(word)
+            }
+        };
+
+        if inserted {
+            self.words_count += 1;
+        }
+
+        inserted
+    }
+
+    /// remove a word and prune the branches left without words
+    fn remove_impl(&mut self, mut word: Peekable<impl Iterator<Item = Char>>) -> bool {
+        let removed = match word.peek().cloned() {
+            None => std::mem::replace(&mut self.word_end, false),
+            Some(next_char) => {
+                let child_idx = self.child_idx(&mut word, &next_char);
+
+                let Some(child) = &mut self.children[child_idx] else {
+                    return false;
+                };
+
+                let result = child.remove_impl(word);
+                if child.words_count == 0 {
+                    self.children[child_idx] = None;
+                }
+                result
             }
         };
 
-        let child_idx = match next_char.cmp(&self.character) {
+        if removed {
+            self.words_count -= 1;
+        }
+
+        removed
+    }
+
+    /// index of the child to go to with the next char.
+    /// The char is consumed if it matches the node
+    fn child_idx(
+        &self,
+        word: &mut Peekable<impl Iterator<Item = Char>>,
+        next_char: &Char,
+    ) -> usize {
+        match next_char.cmp(&self.character) {
             Ordering::Less => 0,
             Ordering::Equal => {
                 word.next();
                 1
             }
             Ordering::Greater => 2,
-        };
-
-        self.children[child_idx]
-            .get_or_insert(Box::new(TernaryTrieNode::new(next_char.clone(), false)))
-            .insert_impl(word)
+        }
     }
 
     fn find_prefix_impl(&self, mut word: Peekable<impl Iterator<Item = Char>>) -> Option<&Self> {
@@ -62,14 +103,7 @@ where
             return Some(self);
         };
 
-        let child_idx = match next_char.cmp(&self.character) {
-            Ordering::Less => 0,
-            Ordering::Equal => {
-                word.next();
-                1
-            }
-            Ordering::Greater => 2,
-        };
+        let child_idx = self.child_idx(&mut word, &next_char);
 
         let Some(child) = &self.children[child_idx] else {
             return None;
@@ -81,6 +115,11 @@ where
     pub fn word_end(&self) -> bool {
         self.word_end
     }
+
+    /// number of words starting at this node
+    pub fn words_count(&self) -> usize {
+        self.words_count
+    }
 }
 
 impl<Char> TernaryTrieNode<Char>
@@ -91,10 +130,8 @@ where
         let space = "| ".repeat(indent);
         f.write_fmt(format_args!("{}{:?}\n", space, self.character))?;
 
-        for node in &self.children {
-            if let Some(node) = node {
-                node.format_impl(indent + 1, f)?;
-            }
+        for node in self.children.iter().flatten() {
+            node.format_impl(indent + 1, f)?;
         }
 
         Ok(())
@@ -125,6 +162,42 @@ where
     }
 }
 
+/// Iterator over words of a [`TernaryTrie`] in lexicographic order
+pub struct TernaryTrieWords<'a, Char> {
+    /// nodes to visit with the words leading to them
+    stack: Vec<(&'a TernaryTrieNode<Char>, Vec<Char>)>,
+}
+
+impl<'a, Char: Clone> Iterator for TernaryTrieWords<'a, Char> {
+    type Item = Vec<Char>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((node, word)) = self.stack.pop() {
+            // words ending at the node are shorter than anything below it,
+            // and only the middle child extends the word with the node's char
+            let [less, equal, greater] = &node.children;
+
+            if let Some(greater) = greater {
+                self.stack.push((greater, word.clone()));
+            }
+            if let Some(equal) = equal {
+                let mut equal_word = word.clone();
+                equal_word.push(node.character.clone());
+                self.stack.push((equal, equal_word));
+            }
+            if let Some(less) = less {
+                self.stack.push((less, word.clone()));
+            }
+
+            if node.word_end {
+                return Some(word);
+            }
+        }
+
+        None
+    }
+}
+
 impl<Char> Trie for TernaryTrie<Char>
 where
     Char: Ord + Clone,
@@ -152,6 +225,74 @@ where
     fn len(&self) -> usize {
         self.words_count
     }
+
+    fn remove(&mut self, word: impl Iterator<Item = Self::Char>) -> bool {
+        let removed = self.root.remove_impl(word.peekable());
+
+        if removed {
+            self.words_count -= 1;
+        }
+
+        removed
+    }
+
+    fn count_with_prefix(&self, prefix: impl Iterator<Item = Self::Char>) -> usize {
+        self.root
+            .find_prefix(prefix)
+            .map_or(0, |node| node.words_count)
+    }
+
+    fn longest_prefix_of(&self, word: impl Iterator<Item = Self::Char>) -> Option<Vec<Self::Char>> {
+        let mut word = word.peekable();
+        let mut node = &self.root;
+        let mut consumed = vec![];
+        let mut longest = node.word_end.then_some(0);
+
+        while let Some(next_char) = word.peek().cloned() {
+            let child_idx = node.child_idx(&mut word, &next_char);
+            if child_idx == 1 {
+                consumed.push(next_char);
+            }
+
+            let Some(child) = &node.children[child_idx] else {
+                break;
+            };
+
+            node = child;
+            if node.word_end {
+                longest = Some(consumed.len());
+            }
+        }
+
+        longest.map(|len| {
+            consumed.truncate(len);
+            consumed
+        })
+    }
+}
+
+impl<Char> OrderedTrie for TernaryTrie<Char>
+where
+    Char: Ord + Clone,
+{
+    fn iter(&self) -> impl Iterator<Item = Vec<Self::Char>> + '_ {
+        self.iter_prefix(std::iter::empty())
+    }
+
+    fn iter_prefix(
+        &self,
+        prefix: impl Iterator<Item = Self::Char>,
+    ) -> impl Iterator<Item = Vec<Self::Char>> + '_ {
+        let prefix: Vec<_> = prefix.collect();
+        let stack = self
+            .root
+            .find_prefix(prefix.iter().cloned())
+            .map(|node| (node, prefix))
+            .into_iter()
+            .collect();
+
+        TernaryTrieWords { stack }
+    }
 }
 
 impl<Char> Default for TernaryTrie<Char>
@@ -0,0 +1,302 @@
+use std::collections::BTreeMap;
+
+use super::PrefixMap;
+
+#[derive(Debug, Clone)]
+struct TrieMapNode<Char, V> {
+    value: Option<V>,
+    /// number of keys in the subtree
+    keys_count: usize,
+    children: BTreeMap<Char, TrieMapNode<Char, V>>,
+}
+
+impl<Char, V> TrieMapNode<Char, V> {
+    fn new() -> Self {
+        TrieMapNode {
+            value: None,
+            keys_count: 0,
+            children: BTreeMap::new(),
+        }
+    }
+}
+
+impl<Char: Ord, V> TrieMapNode<Char, V> {
+    fn find_prefix(&self, prefix: impl Iterator<Item = Char>) -> Option<&Self> {
+        let mut node = self;
+
+        for character in prefix {
+            node = node.children.get(&character)?;
+        }
+
+        Some(node)
+    }
+
+    fn find_prefix_mut(&mut self, prefix: impl Iterator<Item = Char>) -> Option<&mut Self> {
+        let mut node = self;
+
+        for character in prefix {
+            node = node.children.get_mut(&character)?;
+        }
+
+        Some(node)
+    }
+
+    /// insert a value for a key which isn't in the subtree yet
+    fn insert_new(&mut self, key: impl Iterator<Item = Char>, value: V) {
+        let mut node = self;
+        node.keys_count += 1;
+
+        for character in key {
+            node = node.children.entry(character).or_insert_with(Self::new);
+            node.keys_count += 1;
+        }
+
+        node.value = Some(value);
+    }
+
+    /// remove a key and prune the branches left without keys
+    fn remove_impl(&mut self, mut key: impl Iterator<Item = Char>) -> Option<V> {
+        let removed = match key.next() {
+            Some(next_char) => {
+                let next_node = self.children.get_mut(&next_char)?;
+
+                let result = next_node.remove_impl(key);
+                if next_node.keys_count == 0 {
+                    self.children.remove(&next_char);
+                }
+                result
+            }
+            None => self.value.take(),
+        };
+
+        if removed.is_some() {
+            self.keys_count -= 1;
+        }
+
+        removed
+    }
+}
+
+/// Map from sequences of chars to values.
+/// Children of every node are ordered, so keys are iterated in lexicographic order
+#[derive(Debug, Clone)]
+pub struct TrieMap<Char, V> {
+    root: TrieMapNode<Char, V>,
+}
+
+impl<Char: Ord + Clone, V> TrieMap<Char, V> {
+    pub fn new() -> Self {
+        TrieMap {
+            root: TrieMapNode::new(),
+        }
+    }
+}
+
+impl<Char: Ord + Clone, V> Default for TrieMap<Char, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Iterator over keys and values of a [`TrieMap`] in lexicographic order of keys
+pub struct TrieMapIter<'a, Char, V> {
+    /// nodes to visit with the keys leading to them
+    stack: Vec<(&'a TrieMapNode<Char, V>, Vec<Char>)>,
+}
+
+impl<'a, Char: Clone, V> Iterator for TrieMapIter<'a, Char, V> {
+    type Item = (Vec<Char>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((node, key)) = self.stack.pop() {
+            for (character, child) in node.children.iter().rev() {
+                let mut child_key = key.clone();
+                child_key.push(character.clone());
+                self.stack.push((child, child_key));
+            }
+
+            if let Some(value) = &node.value {
+                return Some((key, value));
+            }
+        }
+
+        None
+    }
+}
+
+impl<Char: Ord + Clone, V> PrefixMap for TrieMap<Char, V> {
+    type Char = Char;
+    type Value = V;
+
+    fn insert(&mut self, key: impl Iterator<Item = Char>, value: V) -> Option<V> {
+        let key: Vec<_> = key.collect();
+
+        if let Some(existing) = self.get_mut(key.iter().cloned()) {
+            return Some(std::mem::replace(existing, value));
+        }
+
+        self.root.insert_new(key.into_iter(), value);
+        None
+    }
+
+    fn get(&self, key: impl Iterator<Item = Char>) -> Option<&V> {
+        self.root.find_prefix(key)?.value.as_ref()
+    }
+
+    fn get_mut(&mut self, key: impl Iterator<Item = Char>) -> Option<&mut V> {
+        self.root.find_prefix_mut(key)?.value.as_mut()
+    }
+
+    fn remove(&mut self, key: impl Iterator<Item = Char>) -> Option<V> {
+        self.root.remove_impl(key)
+    }
+
+    fn len(&self) -> usize {
+        self.root.keys_count
+    }
+
+    fn count_with_prefix(&self, prefix: impl Iterator<Item = Char>) -> usize {
+        self.root
+            .find_prefix(prefix)
+            .map_or(0, |node| node.keys_count)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (Vec<Char>, &V)> + '_ {
+        self.iter_prefix(std::iter::empty())
+    }
+
+    fn iter_prefix(
+        &self,
+        prefix: impl Iterator<Item = Char>,
+    ) -> impl Iterator<Item = (Vec<Char>, &V)> + '_ {
+        let prefix: Vec<_> = prefix.collect();
+        let stack = self
+            .root
+            .find_prefix(prefix.iter().cloned())
+            .map(|node| (node, prefix))
+            .into_iter()
+            .collect();
+
+        TrieMapIter { stack }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TrieMap;
+    use crate::trie::StringMap;
+
+    #[test]
+    fn insert_replaces_values() {
+        let mut map = TrieMap::new();
+
+        assert_eq!(map.insert("car", 1), None);
+        assert_eq!(map.insert("cart", 2), None);
+        assert_eq!(map.insert("car", 3), Some(1));
+
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get("car"), Some(&3));
+        assert_eq!(map.get("ca"), None);
+        assert_eq!(map.get("carts"), None);
+    }
+
+    #[test]
+    fn get_mut_and_remove() {
+        let mut map = TrieMap::new();
+        map.insert("a", 1);
+        map.insert("ab", 2);
+
+        *map.get_mut("ab").unwrap() += 10;
+        assert_eq!(map.remove("a"), Some(1));
+        assert_eq!(map.remove("a"), None);
+        assert_eq!(map.remove("abc"), None);
+
+        assert!(!map.contains_key("a"));
+        assert_eq!(map.get("ab"), Some(&12));
+        assert_eq!(map.count_with_prefix("a"), 1);
+    }
+
+    #[test]
+    fn entry_counts_words() {
+        let mut counts = TrieMap::new();
+        for word in "the cat and the dog and the bird".split(' ') {
+            *counts.entry(word).or_default() += 1;
+        }
+
+        let entries: Vec<_> = counts.iter_prefix("").collect();
+        assert_eq!(
+            entries,
+            vec![
+                ("and".to_owned(), &2),
+                ("bird".to_owned(), &1),
+                ("cat".to_owned(), &1),
+                ("dog".to_owned(), &1),
+                ("the".to_owned(), &3),
+            ]
+        );
+    }
+
+    #[test]
+    fn entry_modifies_existing_values_only() {
+        let mut map = TrieMap::new();
+        map.insert("x", 1);
+
+        map.entry("x").and_modify(|v| *v *= 10).or_insert(0);
+        map.entry("y").and_modify(|v| *v *= 10).or_insert(5);
+
+        assert_eq!(map.get("x"), Some(&10));
+        assert_eq!(map.get("y"), Some(&5));
+    }
+
+    #[test]
+    fn prefix_iteration_is_sorted() {
+        let mut scores = TrieMap::new();
+        for (word, score) in [("tea", 3), ("ted", 4), ("ten", 12), ("to", 7), ("i", 11)] {
+            scores.insert(word, score);
+        }
+
+        let completions: Vec<_> = scores.iter_prefix("te").collect();
+        assert_eq!(
+            completions,
+            vec![
+                ("tea".to_owned(), &3),
+                ("ted".to_owned(), &4),
+                ("ten".to_owned(), &12)
+            ]
+        );
+        assert_eq!(scores.iter_prefix("x").count(), 0);
+    }
+
+    mod prefix_map {
+        use std::collections::BTreeMap;
+
+        use proptest::prelude::*;
+
+        use crate::trie::{trie_map::TrieMap, PrefixMap};
+
+        proptest! {
+            #[test]
+            fn trie_map_matches_model(operations in prop::collection::vec((any::<bool>(), "[abc]{0,4}", 0..100i32), 0..50)) {
+                let mut map = TrieMap::new();
+                let mut model = BTreeMap::new();
+
+                for (insert, key, value) in operations {
+                    if insert {
+                        prop_assert_eq!(map.insert(key.chars(), value), model.insert(key, value));
+                    } else {
+                        prop_assert_eq!(map.remove(key.chars()), model.remove(&key));
+                    }
+                }
+
+                prop_assert_eq!(map.len(), model.len());
+                let entries: Vec<_> = map.iter().map(|(key, value)| (String::from_iter(key), *value)).collect();
+                prop_assert_eq!(entries, model.clone().into_iter().collect::<Vec<_>>());
+
+                for prefix in ["a", "b", "ab", "ba"] {
+                    let expected = model.keys().filter(|key| key.starts_with(prefix)).count();
+                    prop_assert_eq!(map.count_with_prefix(prefix.chars()), expected);
+                }
+            }
+        }
+    }
+}
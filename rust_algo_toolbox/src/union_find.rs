@@ -1,8 +1,16 @@
+mod aggregate_uf;
+mod concurrent_uf;
 mod dense_uf;
+mod potential_uf;
+mod rollback_uf;
 mod sparse_uf;
 use std::collections::HashSet;
 
+pub use aggregate_uf::{Aggregate, AggregateUF, BoundingBox, Max, Min, Sum};
+pub use concurrent_uf::ConcurrentUF;
 pub use dense_uf::*;
+pub use potential_uf::{Contradiction, PotentialUF};
+pub use rollback_uf::{RollbackUF, Snapshot};
 pub use sparse_uf::SparseUF;
 
 #[derive(Debug)]
@@ -68,6 +76,21 @@ mod tests {
         Box::new(DenseUF::new(MAX_NODES))
     }
 
+    #[fixture]
+    fn empty_set_rollback() -> DynUF {
+        Box::new(RollbackUF::new(MAX_NODES))
+    }
+
+    #[fixture]
+    fn empty_set_aggregate() -> DynUF {
+        Box::new(AggregateUF::new(MAX_NODES, |_| ()))
+    }
+
+    #[fixture]
+    fn empty_set_concurrent() -> DynUF {
+        Box::new(ConcurrentUF::new(MAX_NODES))
+    }
+
     /// sparse uf with disjoin set of values from 0 to 99
     #[fixture]
     fn set_100_orphans_sparse() -> DynUF {
@@ -92,6 +115,15 @@ mod tests {
         Box::new(empty_set_dense)
     }
 
+    /// uf with disjoin set of values from 0 to 99
+    fn orphans<U: PrintableUF + 'static>(mut uf: U) -> DynUF {
+        for i in 0..100 {
+            uf.insert(i);
+        }
+
+        Box::new(uf)
+    }
+
     fn rand_permutation(from: usize, to: usize) -> Vec<usize> {
         let mut rng = thread_rng();
         let mut vec: Vec<usize> = (from..to).collect();
@@ -162,11 +194,33 @@ mod tests {
         set
     }
 
+    /// uf with values from 0 to 99 joined into 10 clusters of consecutive values
+    fn clusters_10by10<U: PrintableUF + 'static>(uf: U) -> DynUF {
+        let mut set = orphans(uf);
+
+        for cluster_idx in 0..10 {
+            let from = (cluster_idx * 10) as usize;
+            let to = ((cluster_idx + 1) * 10) as usize;
+            let nodes = rand_permutation(from, to);
+            let links = chain_nodes(&nodes);
+            let links = randomize_links(links);
+
+            for (a, b) in links {
+                set.join(a, b);
+            }
+        }
+
+        set
+    }
+
     ////////////////////////////////////////////////////////////////////
 
     #[rstest]
     #[case(empty_set_sparse())]
     #[case(empty_set_dense())]
+    #[case(empty_set_rollback())]
+    #[case(empty_set_aggregate())]
+    #[case(empty_set_concurrent())]
     fn insert_new_increments_size(#[case] empty_set: DynUF) {
         let mut uf = empty_set;
         for i in 0..10 {
@@ -179,6 +233,9 @@ mod tests {
     #[rstest]
     #[case(empty_set_sparse())]
     #[case(empty_set_dense())]
+    #[case(empty_set_rollback())]
+    #[case(empty_set_aggregate())]
+    #[case(empty_set_concurrent())]
     fn insert_existing_doesnt_change_size(#[case] empty_set: DynUF) {
         let mut uf = empty_set;
         for i in 0..10 {
@@ -211,6 +268,9 @@ mod tests {
     #[rstest]
     #[case(empty_set_sparse())]
     #[case(empty_set_dense())]
+    #[case(empty_set_rollback())]
+    #[case(empty_set_aggregate())]
+    #[case(empty_set_concurrent())]
     fn items_in_empty_set_are_disconnected(#[case] mut empty_set: DynUF) {
         for i in 0..10 {
             for j in (i + 1)..10 {
@@ -222,6 +282,9 @@ mod tests {
     #[rstest]
     #[case(set_100_orphans_sparse())]
     #[case(set_100_orphans_dense())]
+    #[case(orphans(RollbackUF::new(100)))]
+    #[case(orphans(AggregateUF::new(100, |_| ())))]
+    #[case(orphans(ConcurrentUF::new(100)))]
     fn inserted_items_are_disconnected(#[case] mut set_100_orphans: DynUF) {
         for i in 0..100 {
             for j in (i + 1)..100 {
@@ -234,6 +297,9 @@ mod tests {
     #[rstest]
     #[case(set_10by10_sparse())]
     #[case(set_10by10_dense())]
+    #[case(clusters_10by10(RollbackUF::new(100)))]
+    #[case(clusters_10by10(AggregateUF::new(100, |_| ())))]
+    #[case(clusters_10by10(ConcurrentUF::new(100)))]
     fn connected_returns_true_for_connected_nodes(#[case] mut set_10by10: DynUF) {
         for id1 in 0..100 {
             let cluster_min = (id1 / 10) * 10;
@@ -247,6 +313,9 @@ mod tests {
     #[rstest]
     #[case(set_10by10_sparse())]
     #[case(set_10by10_dense())]
+    #[case(clusters_10by10(RollbackUF::new(100)))]
+    #[case(clusters_10by10(AggregateUF::new(100, |_| ())))]
+    #[case(clusters_10by10(ConcurrentUF::new(100)))]
     fn clusters_count_is_correct(#[case] mut set_10by10: DynUF) {
         assert_returns!(10, UnionFind::clusters_count, &mut *set_10by10);
     }
@@ -254,6 +323,9 @@ mod tests {
     #[rstest]
     #[case(set_10by10_sparse())]
     #[case(set_10by10_dense())]
+    #[case(clusters_10by10(RollbackUF::new(100)))]
+    #[case(clusters_10by10(AggregateUF::new(100, |_| ())))]
+    #[case(clusters_10by10(ConcurrentUF::new(100)))]
     fn clusters_content_is_correct(#[case] mut set_10by10: DynUF) {
         let mut clusters: Vec<Cluster> = set_10by10.clusters();
         clusters.sort_by_key(|cluster| cluster.id);
@@ -267,6 +339,9 @@ mod tests {
     #[rstest]
     #[case(set_10by10_sparse())]
     #[case(set_10by10_dense())]
+    #[case(clusters_10by10(RollbackUF::new(100)))]
+    #[case(clusters_10by10(AggregateUF::new(100, |_| ())))]
+    #[case(clusters_10by10(ConcurrentUF::new(100)))]
     fn joining_n_clusters_makes_single_cluster(#[case] mut set_10by10: DynUF) {
         let mut rng = thread_rng();
 
@@ -287,6 +362,9 @@ mod tests {
     #[rstest]
     #[case(empty_set_sparse())]
     #[case(empty_set_dense())]
+    #[case(empty_set_rollback())]
+    #[case(empty_set_aggregate())]
+    #[case(empty_set_concurrent())]
     fn disjoint_set_gives_unit_clusters(#[case] empty_set: DynUF) {
         let mut uf = empty_set;
         for i in 0..10 {
@@ -303,6 +381,9 @@ mod tests {
     #[rstest]
     #[case(set_100_orphans_sparse())]
     #[case(set_100_orphans_dense())]
+    #[case(orphans(RollbackUF::new(100)))]
+    #[case(orphans(AggregateUF::new(100, |_| ())))]
+    #[case(orphans(ConcurrentUF::new(100)))]
     fn joining_items_makes_their_ids_equal(#[case] mut set_100_orphans: DynUF) {
         let mut rng = thread_rng();
         let id_distr1 = rand::distributions::Uniform::from(0..100);
@@ -323,6 +404,9 @@ mod tests {
     #[rstest]
     #[case(empty_set_sparse())]
     #[case(empty_set_dense())]
+    #[case(empty_set_rollback())]
+    #[case(empty_set_aggregate())]
+    #[case(empty_set_concurrent())]
     fn item_has_id_of_its_terminal_link(#[case] mut uf: DynUF) {
         uf.join(1, 2);
         uf.join(2, 3);
@@ -337,6 +421,9 @@ mod tests {
     #[rstest]
     #[case(empty_set_sparse())]
     #[case(empty_set_dense())]
+    #[case(empty_set_rollback())]
+    #[case(empty_set_aggregate())]
+    #[case(empty_set_concurrent())]
     fn clusters_are_valid_manual(#[case] empty_set: DynUF) {
         let mut uf = empty_set;
 
@@ -0,0 +1,352 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    ops::AddAssign,
+};
+
+use super::{Cluster, UnionFind};
+
+/// Summary of a cluster's items which can be combined when clusters are joined.
+/// Merging must be associative and commutative, so the result doesn't depend on the order of joins
+pub trait Aggregate {
+    /// absorb the aggregate of another cluster
+    fn merge(&mut self, other: Self);
+}
+
+impl Aggregate for () {
+    fn merge(&mut self, _other: Self) {}
+}
+
+impl<A: Aggregate, B: Aggregate> Aggregate for (A, B) {
+    fn merge(&mut self, other: Self) {
+        self.0.merge(other.0);
+        self.1.merge(other.1);
+    }
+}
+
+impl<A: Aggregate, B: Aggregate, C: Aggregate> Aggregate for (A, B, C) {
+    fn merge(&mut self, other: Self) {
+        self.0.merge(other.0);
+        self.1.merge(other.1);
+        self.2.merge(other.2);
+    }
+}
+
+/// Sum of the items' values
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Sum<T>(pub T);
+
+impl<T: AddAssign> Aggregate for Sum<T> {
+    fn merge(&mut self, other: Self) {
+        self.0 += other.0;
+    }
+}
+
+/// Minimum of the items' values
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Min<T>(pub T);
+
+impl<T: PartialOrd> Aggregate for Min<T> {
+    fn merge(&mut self, other: Self) {
+        if other.0 < self.0 {
+            self.0 = other.0;
+        }
+    }
+}
+
+/// Maximum of the items' values
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Max<T>(pub T);
+
+impl<T: PartialOrd> Aggregate for Max<T> {
+    fn merge(&mut self, other: Self) {
+        if other.0 > self.0 {
+            self.0 = other.0;
+        }
+    }
+}
+
+/// Axis-aligned bounding box of the items' 2d positions
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BoundingBox<T> {
+    pub min: (T, T),
+    pub max: (T, T),
+}
+
+impl<T: Copy> BoundingBox<T> {
+    /// bounding box of a single point
+    pub fn point(x: T, y: T) -> Self {
+        BoundingBox {
+            min: (x, y),
+            max: (x, y),
+        }
+    }
+}
+
+impl<T: Copy + PartialOrd> Aggregate for BoundingBox<T> {
+    fn merge(&mut self, other: Self) {
+        let min = |a: T, b: T| if b < a { b } else { a };
+        let max = |a: T, b: T| if b > a { b } else { a };
+
+        self.min = (min(self.min.0, other.min.0), min(self.min.1, other.min.1));
+        self.max = (max(self.max.0, other.max.0), max(self.max.1, other.max.1));
+    }
+}
+
+type Init<A> = Box<dyn Fn(usize) -> A>;
+type MergeCallback<A> = Box<dyn FnMut(usize, usize, &A)>;
+
+/// Dense disjoint set union keeping an [`Aggregate`] and the size of every cluster up to date,
+/// so they can be queried without enumerating the cluster's items
+pub struct AggregateUF<A> {
+    parents: Vec<Option<usize>>,
+    cluster_sizes: Vec<u32>,
+    /// aggregates are stored for cluster roots only
+    aggregates: Vec<Option<A>>,
+    size: usize,
+    init: Init<A>,
+    on_merge: Option<MergeCallback<A>>,
+}
+
+// public
+impl<A: Aggregate> AggregateUF<A> {
+    /// create an empty disjoint set union with a given capacity.
+    /// `init` computes the aggregate of a single item when it's inserted
+    pub fn new(capacity: usize, init: impl Fn(usize) -> A + 'static) -> Self {
+        AggregateUF {
+            parents: vec![None; capacity],
+            cluster_sizes: vec![0; capacity],
+            aggregates: (0..capacity).map(|_| None).collect(),
+            size: 0,
+            init: Box::new(init),
+            on_merge: None,
+        }
+    }
+
+    /// call `callback(new_root, absorbed_root, merged_aggregate)` every time two clusters are joined
+    pub fn with_merge_callback(mut self, callback: impl FnMut(usize, usize, &A) + 'static) -> Self {
+        self.on_merge = Some(Box::new(callback));
+        self
+    }
+
+    /// capacity of the DSU.
+    /// The maximum ID that can be inserted is `capacity - 1`
+    pub fn capacity(&self) -> usize {
+        self.parents.len()
+    }
+
+    /// insert (disconnected) item with a given aggregate instead of the one computed by `init`.
+    /// The aggregate of an already inserted item isn't changed
+    pub fn insert_with(&mut self, item: usize, aggregate: A) {
+        assert!(item < self.capacity());
+
+        if self.parents[item].is_none() {
+            self.parents[item] = Some(item);
+            self.cluster_sizes[item] = 1;
+            self.aggregates[item] = Some(aggregate);
+            self.size += 1;
+        }
+    }
+
+    /// number of items in the item's cluster, or zero if it isn't inserted
+    pub fn cluster_size_of(&mut self, item: usize) -> usize {
+        self.cluster_id_of(item)
+            .map_or(0, |root| self.cluster_sizes[root] as usize)
+    }
+
+    /// aggregate of the item's cluster if it's inserted
+    pub fn aggregate_of(&mut self, item: usize) -> Option<&A> {
+        let root = self.cluster_id_of(item)?;
+
+        self.aggregates[root].as_ref()
+    }
+
+    /// iterator over cluster ids with their aggregates
+    pub fn aggregates(&self) -> impl Iterator<Item = (usize, &A)> {
+        self.aggregates
+            .iter()
+            .enumerate()
+            .filter_map(|(root, aggregate)| aggregate.as_ref().map(|aggregate| (root, aggregate)))
+    }
+}
+
+impl<A: Debug> Debug for AggregateUF<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AggregateUF")
+            .field("parents", &self.parents)
+            .field("cluster_sizes", &self.cluster_sizes)
+            .field("aggregates", &self.aggregates)
+            .field("size", &self.size)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<A: Aggregate> UnionFind for AggregateUF<A> {
+    fn len(&self) -> usize {
+        self.size
+    }
+
+    fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    fn insert(&mut self, item: usize) {
+        if !self.contains(item) {
+            let aggregate = (self.init)(item);
+            self.insert_with(item, aggregate);
+        }
+    }
+
+    fn contains(&self, item: usize) -> bool {
+        item < self.capacity() && self.parents[item].is_some()
+    }
+
+    fn cluster_id_of(&mut self, item: usize) -> Option<usize> {
+        assert!(item < self.capacity());
+
+        let mut current = item;
+        let mut parent = self.parents[current]?;
+
+        while current != parent {
+            let grandparent = self.parents[parent].unwrap();
+
+            // shorten path towards the root skipping one intermediate node
+            self.parents[current] = Some(grandparent);
+            current = parent;
+            parent = grandparent;
+        }
+
+        Some(current)
+    }
+
+    fn join(&mut self, item1: usize, item2: usize) -> usize {
+        self.insert(item1);
+        self.insert(item2);
+
+        let root1 = self.cluster_id_of(item1).unwrap();
+        let root2 = self.cluster_id_of(item2).unwrap();
+
+        if root1 == root2 {
+            return root1;
+        }
+
+        let (child, root) = if self.cluster_sizes[root1] < self.cluster_sizes[root2] {
+            (root1, root2)
+        } else {
+            (root2, root1)
+        };
+
+        self.parents[child] = Some(root);
+        self.cluster_sizes[root] += self.cluster_sizes[child];
+        self.cluster_sizes[child] = 0;
+
+        let absorbed = self.aggregates[child].take().unwrap();
+        let merged = self.aggregates[root].as_mut().unwrap();
+        merged.merge(absorbed);
+
+        if let Some(callback) = self.on_merge.as_mut() {
+            callback(root, child, merged);
+        }
+
+        root
+    }
+
+    fn connected(&mut self, item1: usize, item2: usize) -> bool {
+        if !self.contains(item1) || !self.contains(item2) {
+            return false;
+        }
+
+        self.cluster_id_of(item1) == self.cluster_id_of(item2)
+    }
+
+    fn clusters(&mut self) -> Vec<Cluster> {
+        let mut clusters = HashMap::<usize, HashSet<usize>>::new();
+
+        for id in 0..self.capacity() {
+            if let Some(cluster_id) = self.cluster_id_of(id) {
+                clusters.entry(cluster_id).or_default().insert(id);
+            }
+        }
+
+        clusters
+            .into_iter()
+            .map(|(id, nodes)| Cluster { id, nodes })
+            .collect()
+    }
+
+    fn clusters_count(&mut self) -> usize {
+        self.aggregates.iter().filter(|it| it.is_some()).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use proptest::{prop_assert_eq, proptest};
+
+    use super::*;
+
+    #[test]
+    fn aggregates_are_merged_on_join() {
+        let mut set = AggregateUF::new(10, |item| (Sum(item), Min(item), Max(item)));
+        set.join(3, 5);
+        set.join(7, 5);
+        set.join(1, 2);
+
+        assert_eq!(set.aggregate_of(3), Some(&(Sum(15), Min(3), Max(7))));
+        assert_eq!(set.aggregate_of(2), Some(&(Sum(3), Min(1), Max(2))));
+        assert_eq!(set.aggregate_of(0), None);
+        assert_eq!(set.cluster_size_of(7), 3);
+        assert_eq!(set.cluster_size_of(0), 0);
+        assert_eq!(set.aggregates().count(), 2);
+    }
+
+    #[test]
+    fn bounding_box_covers_all_points() {
+        let width = 4;
+        let mut set = AggregateUF::new(16, move |item| {
+            BoundingBox::point(item % width, item / width)
+        });
+        set.insert_with(15, BoundingBox::point(10, 10));
+        set.join(5, 6);
+        set.join(6, 10);
+        set.join(10, 15);
+
+        let bbox = set.aggregate_of(5).unwrap();
+        assert_eq!(bbox.min, (1, 1));
+        assert_eq!(bbox.max, (10, 10));
+    }
+
+    #[test]
+    fn merge_callback_sees_every_join() {
+        let merges = Rc::new(RefCell::new(vec![]));
+        let log = merges.clone();
+        let mut set = AggregateUF::new(10, |_| Sum(1)).with_merge_callback(
+            move |root, absorbed, sum: &Sum<i32>| log.borrow_mut().push((root, absorbed, sum.0)),
+        );
+
+        set.join(0, 1);
+        set.join(1, 0);
+        set.join(2, 1);
+
+        assert_eq!(*merges.borrow(), vec![(0, 1, 2), (0, 2, 3)]);
+    }
+
+    proptest! {
+        #[test]
+        fn size_aggregate_matches_cluster_size(
+            pairs in proptest::collection::vec((0usize..50, 0usize..50), 0..100),
+        ) {
+            let mut set = AggregateUF::new(50, |_| Sum(1));
+            for (a, b) in pairs {
+                set.join(a, b);
+            }
+
+            for cluster in set.clusters() {
+                prop_assert_eq!(set.aggregate_of(cluster.id), Some(&Sum(cluster.nodes.len())));
+                prop_assert_eq!(set.cluster_size_of(cluster.id), cluster.nodes.len());
+            }
+        }
+    }
+}
@@ -0,0 +1,248 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+
+use super::{Cluster, UnionFind};
+
+/// Lock-free disjoint set union which can be shared between threads.
+///
+/// Roots are always linked under the root with the smaller id, so concurrent joins can't create cycles.
+/// `find` is wait-free: it follows parent links, shortening the path with at most one CAS per step,
+/// and `join` retries linking with CAS until the roots it observed are still roots
+#[derive(Debug)]
+pub struct ConcurrentUF {
+    parents: Vec<AtomicUsize>,
+    inserted: Vec<AtomicBool>,
+    size: AtomicUsize,
+}
+
+// public
+impl ConcurrentUF {
+    /// create an empty disjoint set union with a given capacity
+    pub fn new(capacity: usize) -> ConcurrentUF {
+        ConcurrentUF {
+            parents: (0..capacity).map(AtomicUsize::new).collect(),
+            inserted: (0..capacity).map(|_| AtomicBool::new(false)).collect(),
+            size: AtomicUsize::new(0),
+        }
+    }
+
+    /// capacity of the DSU.
+    /// The maximum ID that can be inserted is `capacity - 1`
+    pub fn capacity(&self) -> usize {
+        self.parents.len()
+    }
+
+    /// get the number of items in set
+    pub fn len(&self) -> usize {
+        self.size.load(Ordering::Acquire)
+    }
+
+    /// check if the set is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// insert (disconnected) item to the set
+    pub fn insert(&self, item: usize) {
+        assert!(item < self.capacity());
+
+        if !self.inserted[item].swap(true, Ordering::AcqRel) {
+            self.size.fetch_add(1, Ordering::AcqRel);
+        }
+    }
+
+    /// check if the set contains the item
+    pub fn contains(&self, item: usize) -> bool {
+        item < self.capacity() && self.inserted[item].load(Ordering::Acquire)
+    }
+
+    /// get the id of the item's connected component if it's inserted
+    /// otherwise return None
+    pub fn find(&self, item: usize) -> Option<usize> {
+        self.contains(item).then(|| self.root_of(item))
+    }
+
+    /// join two items and return id of the cluster they are in after joining.
+    /// If some (or both) of the items wasn't present, it's inserted before joining
+    pub fn join(&self, item1: usize, item2: usize) -> usize {
+        self.insert(item1);
+        self.insert(item2);
+
+        loop {
+            let root1 = self.root_of(item1);
+            let root2 = self.root_of(item2);
+
+            if root1 == root2 {
+                return root1;
+            }
+
+            let (child, root) = if root1 < root2 {
+                (root2, root1)
+            } else {
+                (root1, root2)
+            };
+
+            // fails if another thread has linked the child in the meantime
+            if self.parents[child]
+                .compare_exchange(child, root, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+            {
+                return root;
+            }
+        }
+    }
+
+    /// check whether items are connected
+    pub fn connected(&self, item1: usize, item2: usize) -> bool {
+        if !self.contains(item1) || !self.contains(item2) {
+            return false;
+        }
+
+        loop {
+            let root1 = self.root_of(item1);
+            let root2 = self.root_of(item2);
+
+            if root1 == root2 {
+                return true;
+            }
+
+            // roots are different and the first one is still a root, so the items weren't connected
+            if self.parents[root1].load(Ordering::Acquire) == root1 {
+                return false;
+            }
+        }
+    }
+}
+
+// private
+impl ConcurrentUF {
+    fn root_of(&self, item: usize) -> usize {
+        let mut current = item;
+
+        loop {
+            let parent = self.parents[current].load(Ordering::Acquire);
+            if parent == current {
+                return current;
+            }
+
+            let grandparent = self.parents[parent].load(Ordering::Acquire);
+            if grandparent == parent {
+                return parent;
+            }
+
+            // shorten path towards the root skipping one intermediate node.
+            // Failure means another thread has already moved the item closer to the root
+            let _ = self.parents[current].compare_exchange(
+                parent,
+                grandparent,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            );
+            current = grandparent;
+        }
+    }
+}
+
+impl UnionFind for ConcurrentUF {
+    fn len(&self) -> usize {
+        ConcurrentUF::len(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        ConcurrentUF::is_empty(self)
+    }
+
+    fn insert(&mut self, item: usize) {
+        ConcurrentUF::insert(self, item)
+    }
+
+    fn contains(&self, item: usize) -> bool {
+        ConcurrentUF::contains(self, item)
+    }
+
+    fn join(&mut self, item1: usize, item2: usize) -> usize {
+        ConcurrentUF::join(self, item1, item2)
+    }
+
+    fn connected(&mut self, item1: usize, item2: usize) -> bool {
+        ConcurrentUF::connected(self, item1, item2)
+    }
+
+    fn cluster_id_of(&mut self, item: usize) -> Option<usize> {
+        assert!(item < self.capacity());
+
+        self.find(item)
+    }
+
+    fn clusters(&mut self) -> Vec<Cluster> {
+        let mut clusters = HashMap::<usize, HashSet<usize>>::new();
+
+        for id in 0..self.capacity() {
+            if let Some(cluster_id) = self.find(id) {
+                clusters.entry(cluster_id).or_default().insert(id);
+            }
+        }
+
+        clusters
+            .into_iter()
+            .map(|(id, nodes)| Cluster { id, nodes })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use rayon::prelude::*;
+
+    use crate::union_find::{ConcurrentUF, DenseUF, UnionFind};
+
+    #[test]
+    fn parallel_joins_match_sequential_ones() {
+        let n_nodes = 10_000;
+        let mut rng = StdRng::seed_from_u64(7);
+        let pairs: Vec<(usize, usize)> = (0..n_nodes)
+            .map(|_| (rng.gen_range(0..n_nodes), rng.gen_range(0..n_nodes)))
+            .collect();
+
+        let set = ConcurrentUF::new(n_nodes);
+        pairs.par_iter().for_each(|&(a, b)| {
+            set.join(a, b);
+        });
+
+        let mut expected = DenseUF::new(n_nodes);
+        for &(a, b) in &pairs {
+            expected.join(a, b);
+        }
+
+        assert_eq!(set.len(), expected.len());
+        for (id, _) in pairs.iter().take(1000) {
+            for other in 0..20 {
+                assert_eq!(set.connected(*id, other), expected.connected(*id, other));
+            }
+        }
+    }
+
+    #[test]
+    fn concurrent_chain_makes_single_cluster() {
+        let set = ConcurrentUF::new(1000);
+        (1..1000).into_par_iter().for_each(|id| {
+            set.join(id - 1, id);
+        });
+
+        assert!((0..1000).all(|id| set.find(id) == Some(0)));
+    }
+
+    #[test]
+    fn connected_is_false_for_missing_items() {
+        let set = ConcurrentUF::new(10);
+        set.join(1, 2);
+
+        assert!(set.connected(2, 1));
+        assert!(!set.connected(1, 3));
+        assert!(!set.connected(3, 3));
+        assert_eq!(set.find(3), None);
+    }
+}
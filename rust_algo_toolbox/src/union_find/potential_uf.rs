@@ -0,0 +1,250 @@
+use std::{
+    fmt::{Debug, Display},
+    ops::{Add, Sub},
+};
+
+use num_traits::Zero;
+
+/// Error returned when a new constraint contradicts already known ones
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Contradiction<W> {
+    /// difference implied by the previous constraints
+    pub known: W,
+    /// difference requested by the rejected constraint
+    pub requested: W,
+}
+
+impl<W: Display> Display for Contradiction<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "difference is already known to be {}, can't set it to {}",
+            self.known, self.requested
+        )
+    }
+}
+
+impl<W: Debug + Display> std::error::Error for Contradiction<W> {}
+
+/// Disjoint set union tracking a potential of every item relative to its cluster.
+///
+/// Items are joined by constraints `potential(a) - potential(b) = diff`,
+/// so the difference of potentials is known for every pair of items in the same cluster.
+/// Weights are compared exactly, so floating point potentials may report false contradictions
+#[derive(Clone, Debug)]
+pub struct PotentialUF<W> {
+    parents: Vec<Option<usize>>,
+    cluster_sizes: Vec<u32>,
+    /// potential of an item minus potential of its parent
+    potentials: Vec<W>,
+    size: usize,
+}
+
+impl<W> PotentialUF<W>
+where
+    W: Copy + Zero + Add<Output = W> + Sub<Output = W> + PartialEq,
+{
+    /// create an empty disjoint set union with a given capacity
+    pub fn new(capacity: usize) -> Self {
+        PotentialUF {
+            parents: vec![None; capacity],
+            cluster_sizes: vec![0; capacity],
+            potentials: vec![W::zero(); capacity],
+            size: 0,
+        }
+    }
+
+    /// capacity of the DSU.
+    /// The maximum ID that can be inserted is `capacity - 1`
+    pub fn capacity(&self) -> usize {
+        self.parents.len()
+    }
+
+    /// get the number of items in set
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    /// check if the set is empty
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// insert (disconnected) item to the set
+    pub fn insert(&mut self, item: usize) {
+        assert!(item < self.capacity());
+
+        if self.parents[item].is_none() {
+            self.parents[item] = Some(item);
+            self.cluster_sizes[item] = 1;
+            self.potentials[item] = W::zero();
+            self.size += 1;
+        }
+    }
+
+    /// check if the set contains the item
+    pub fn contains(&self, item: usize) -> bool {
+        item < self.capacity() && self.parents[item].is_some()
+    }
+
+    /// get the id of the item's connected component if it's inserted
+    /// otherwise return None
+    pub fn cluster_id_of(&mut self, item: usize) -> Option<usize> {
+        assert!(item < self.capacity());
+
+        self.find(item).map(|(root, _)| root)
+    }
+
+    /// check whether items are connected
+    pub fn connected(&mut self, item1: usize, item2: usize) -> bool {
+        self.diff(item1, item2).is_some()
+    }
+
+    /// add constraint `potential(item1) - potential(item2) = diff` and return id of the joined cluster.
+    /// If some (or both) of the items wasn't present, it's inserted before joining.
+    /// Fails without changing anything if the items are already connected with a different difference
+    pub fn join_with_diff(
+        &mut self,
+        item1: usize,
+        item2: usize,
+        diff: W,
+    ) -> Result<usize, Contradiction<W>> {
+        self.insert(item1);
+        self.insert(item2);
+
+        let (root1, potential1) = self.find(item1).unwrap();
+        let (root2, potential2) = self.find(item2).unwrap();
+
+        if root1 == root2 {
+            let known = potential1 - potential2;
+            if known != diff {
+                return Err(Contradiction {
+                    known,
+                    requested: diff,
+                });
+            }
+
+            return Ok(root1);
+        }
+
+        // potential(root2) - potential(root1)
+        let roots_diff = potential1 - potential2 - diff;
+
+        if self.cluster_sizes[root1] < self.cluster_sizes[root2] {
+            self.attach(root1, root2, W::zero() - roots_diff);
+            Ok(root2)
+        } else {
+            self.attach(root2, root1, roots_diff);
+            Ok(root1)
+        }
+    }
+
+    /// difference `potential(item1) - potential(item2)` if the items are connected
+    pub fn diff(&mut self, item1: usize, item2: usize) -> Option<W> {
+        if !self.contains(item1) || !self.contains(item2) {
+            return None;
+        }
+
+        let (root1, potential1) = self.find(item1)?;
+        let (root2, potential2) = self.find(item2)?;
+
+        (root1 == root2).then(|| potential1 - potential2)
+    }
+}
+
+// private
+impl<W> PotentialUF<W>
+where
+    W: Copy + Zero + Add<Output = W> + Sub<Output = W> + PartialEq,
+{
+    /// root of the item's cluster and potential of the item relative to the root
+    fn find(&mut self, item: usize) -> Option<(usize, W)> {
+        let parent = self.parents[item]?;
+        if parent == item {
+            return Some((item, W::zero()));
+        }
+
+        // union by size keeps the recursion depth logarithmic
+        let (root, parent_potential) = self.find(parent)?;
+        self.parents[item] = Some(root);
+        self.potentials[item] = self.potentials[item] + parent_potential;
+
+        Some((root, self.potentials[item]))
+    }
+
+    fn attach(&mut self, child: usize, root: usize, potential: W) {
+        self.parents[child] = Some(root);
+        self.potentials[child] = potential;
+        self.cluster_sizes[root] += self.cluster_sizes[child];
+        self.cluster_sizes[child] = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::{prop_assert, prop_assert_eq, proptest};
+
+    use super::*;
+
+    #[test]
+    fn diff_is_derived_through_chains() {
+        let mut set = PotentialUF::new(10);
+        set.join_with_diff(0, 1, 3).unwrap();
+        set.join_with_diff(2, 1, 5).unwrap();
+        set.join_with_diff(3, 4, -2).unwrap();
+
+        assert_eq!(set.diff(0, 2), Some(-2));
+        assert_eq!(set.diff(2, 0), Some(2));
+        assert_eq!(set.diff(0, 0), Some(0));
+        assert_eq!(set.diff(0, 3), None);
+        assert_eq!(set.diff(0, 9), None);
+
+        set.join_with_diff(4, 2, 1).unwrap();
+        assert_eq!(set.diff(3, 0), Some(1));
+        assert!(set.connected(0, 4));
+    }
+
+    #[test]
+    fn contradiction_is_detected() {
+        let mut set = PotentialUF::new(10);
+        set.join_with_diff(0, 1, 1).unwrap();
+        set.join_with_diff(1, 2, 1).unwrap();
+
+        assert_eq!(
+            set.join_with_diff(0, 2, 2),
+            Ok(set.cluster_id_of(0).unwrap())
+        );
+        assert_eq!(
+            set.join_with_diff(2, 0, 2),
+            Err(Contradiction {
+                known: -2,
+                requested: 2
+            })
+        );
+        assert_eq!(set.diff(2, 0), Some(-2));
+    }
+
+    proptest! {
+        #[test]
+        fn diffs_match_hidden_potentials(
+            potentials in proptest::collection::vec(-1000i64..1000, 20),
+            pairs in proptest::collection::vec((0usize..20, 0usize..20), 0..60),
+        ) {
+            let mut set = PotentialUF::new(20);
+            for &(a, b) in &pairs {
+                prop_assert!(set.join_with_diff(a, b, potentials[a] - potentials[b]).is_ok());
+            }
+
+            for a in 0..20 {
+                for b in 0..20 {
+                    if let Some(diff) = set.diff(a, b) {
+                        prop_assert_eq!(diff, potentials[a] - potentials[b]);
+                    }
+                }
+            }
+            for &(a, b) in &pairs {
+                prop_assert!(set.join_with_diff(a, b, potentials[a] - potentials[b] + 1).is_err());
+            }
+        }
+    }
+}
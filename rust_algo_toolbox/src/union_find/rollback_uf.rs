@@ -0,0 +1,265 @@
+use std::collections::{HashMap, HashSet};
+
+use super::{Cluster, UnionFind};
+
+/// State of a [`RollbackUF`] which can be restored later
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Snapshot(usize);
+
+#[derive(Clone, Copy, Debug)]
+enum Change {
+    Insert(usize),
+    Join { child: usize, root: usize },
+}
+
+/// Disjoint set union which can undo its operations.
+///
+/// It uses union by size without path compression, so every operation takes O(log n)
+/// and modifies a constant amount of memory, which is recorded to be reverted by [`RollbackUF::rollback`]
+#[derive(Clone, Debug)]
+pub struct RollbackUF {
+    parents: Vec<Option<usize>>,
+    cluster_sizes: Vec<u32>,
+    size: usize,
+    clusters_count: usize,
+    history: Vec<Change>,
+}
+
+// public
+impl RollbackUF {
+    /// create an empty disjoint set union with a given capacity
+    pub fn new(capacity: usize) -> RollbackUF {
+        RollbackUF {
+            parents: vec![None; capacity],
+            cluster_sizes: vec![0; capacity],
+            size: 0,
+            clusters_count: 0,
+            history: vec![],
+        }
+    }
+
+    /// capacity of the DSU.
+    /// The maximum ID that can be inserted is `capacity - 1`
+    pub fn capacity(&self) -> usize {
+        self.parents.len()
+    }
+
+    /// remember the current state
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot(self.history.len())
+    }
+
+    /// undo all insertions and joins made after the snapshot was taken.
+    /// Snapshots taken after this one become invalid
+    pub fn rollback(&mut self, snapshot: Snapshot) {
+        assert!(
+            snapshot.0 <= self.history.len(),
+            "snapshot is newer than the current state"
+        );
+
+        while self.history.len() > snapshot.0 {
+            match self.history.pop().unwrap() {
+                Change::Insert(item) => {
+                    self.parents[item] = None;
+                    self.cluster_sizes[item] = 0;
+                    self.size -= 1;
+                    self.clusters_count -= 1;
+                }
+                Change::Join { child, root } => {
+                    self.parents[child] = Some(child);
+                    self.cluster_sizes[root] -= self.cluster_sizes[child];
+                    self.clusters_count += 1;
+                }
+            }
+        }
+    }
+}
+
+// private
+impl RollbackUF {
+    fn root_of(&self, item: usize) -> Option<usize> {
+        let mut current = item;
+        let mut parent = self.parents[current]?;
+
+        while current != parent {
+            current = parent;
+            parent = self.parents[current].unwrap();
+        }
+
+        Some(current)
+    }
+}
+
+impl UnionFind for RollbackUF {
+    fn len(&self) -> usize {
+        self.size
+    }
+
+    fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    fn insert(&mut self, item: usize) {
+        assert!(item < self.capacity());
+
+        if self.parents[item].is_none() {
+            self.parents[item] = Some(item);
+            self.cluster_sizes[item] = 1;
+            self.size += 1;
+            self.clusters_count += 1;
+            self.history.push(Change::Insert(item));
+        }
+    }
+
+    fn contains(&self, item: usize) -> bool {
+        item < self.capacity() && self.parents[item].is_some()
+    }
+
+    fn join(&mut self, item1: usize, item2: usize) -> usize {
+        self.insert(item1);
+        self.insert(item2);
+
+        let root1 = self.root_of(item1).unwrap();
+        let root2 = self.root_of(item2).unwrap();
+
+        if root1 == root2 {
+            return root1;
+        }
+
+        let (child, root) = if self.cluster_sizes[root1] < self.cluster_sizes[root2] {
+            (root1, root2)
+        } else {
+            (root2, root1)
+        };
+
+        // the child keeps its size, so that it can be subtracted back on rollback
+        self.parents[child] = Some(root);
+        self.cluster_sizes[root] += self.cluster_sizes[child];
+        self.clusters_count -= 1;
+        self.history.push(Change::Join { child, root });
+
+        root
+    }
+
+    fn connected(&mut self, item1: usize, item2: usize) -> bool {
+        if !self.contains(item1) || !self.contains(item2) {
+            return false;
+        }
+
+        self.root_of(item1) == self.root_of(item2)
+    }
+
+    fn cluster_id_of(&mut self, item: usize) -> Option<usize> {
+        assert!(item < self.capacity());
+
+        self.root_of(item)
+    }
+
+    fn clusters(&mut self) -> Vec<Cluster> {
+        let mut clusters = HashMap::<usize, HashSet<usize>>::new();
+
+        for id in 0..self.capacity() {
+            if let Some(cluster_id) = self.root_of(id) {
+                clusters.entry(cluster_id).or_default().insert(id);
+            }
+        }
+
+        clusters
+            .into_iter()
+            .map(|(id, nodes)| Cluster { id, nodes })
+            .collect()
+    }
+
+    fn clusters_count(&mut self) -> usize {
+        self.clusters_count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::{prop_assert_eq, proptest};
+
+    use crate::union_find::{DenseUF, RollbackUF, UnionFind};
+
+    #[test]
+    fn rollback_undoes_joins() {
+        let mut set = RollbackUF::new(10);
+        set.join(0, 1);
+        set.join(2, 3);
+
+        let snapshot = set.snapshot();
+        set.join(1, 2);
+        set.join(3, 4);
+        assert!(set.connected(0, 4));
+        assert_eq!(set.clusters_count(), 1);
+
+        set.rollback(snapshot);
+        assert!(set.connected(0, 1));
+        assert!(set.connected(2, 3));
+        assert!(!set.connected(1, 2));
+        assert!(!set.contains(4));
+        assert_eq!(set.len(), 4);
+        assert_eq!(set.clusters_count(), 2);
+    }
+
+    #[test]
+    fn nested_snapshots_are_restored_in_any_order() {
+        let mut set = RollbackUF::new(10);
+        let empty = set.snapshot();
+        set.join(0, 1);
+        let pair = set.snapshot();
+        set.join(1, 2);
+        set.join(5, 6);
+
+        set.rollback(pair);
+        assert_eq!(set.len(), 2);
+        assert!(set.connected(0, 1));
+
+        set.join(0, 7);
+        assert!(set.connected(1, 7));
+
+        set.rollback(empty);
+        assert!(set.is_empty());
+        assert_eq!(set.clusters_count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "snapshot is newer than the current state")]
+    fn rollback_to_newer_snapshot_panics() {
+        let mut set = RollbackUF::new(10);
+        set.join(0, 1);
+        let snapshot = set.snapshot();
+
+        set.rollback(RollbackUF::new(10).snapshot());
+        set.rollback(snapshot);
+    }
+
+    proptest! {
+        #[test]
+        fn rollback_restores_connectivity(
+            before in proptest::collection::vec((0usize..30, 0usize..30), 0..30),
+            after in proptest::collection::vec((0usize..30, 0usize..30), 0..30),
+        ) {
+            let mut set = RollbackUF::new(30);
+            let mut expected = DenseUF::new(30);
+            for &(a, b) in &before {
+                set.join(a, b);
+                expected.join(a, b);
+            }
+
+            let snapshot = set.snapshot();
+            for &(a, b) in &after {
+                set.join(a, b);
+            }
+            set.rollback(snapshot);
+
+            prop_assert_eq!(set.len(), expected.len());
+            prop_assert_eq!(set.clusters_count(), expected.clusters_count());
+            for a in 0..30 {
+                for b in 0..30 {
+                    prop_assert_eq!(set.connected(a, b), expected.connected(a, b));
+                }
+            }
+        }
+    }
+}
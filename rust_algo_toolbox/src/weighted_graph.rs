@@ -1,8 +1,25 @@
-use std::collections::{BinaryHeap, HashMap, HashSet};
+pub mod all_pairs;
+pub mod connectivity;
+mod csr;
+mod directed;
+pub mod flow;
+pub mod io;
+pub mod mst;
+pub mod shortest_paths;
+pub mod traversal;
 
-use crate::union_find::{SparseUF, UnionFind};
+use std::{collections::HashMap, fmt::Display, ops::Sub};
 
-type VertexID = usize;
+use crate::union_find::Cluster;
+
+use all_pairs::DistanceMatrix;
+pub use csr::CsrGraph;
+pub use directed::DirectedWeightedGraph;
+use flow::{Capacity, MaxFlow};
+use shortest_paths::{NegativeCycle, PathWeight, ShortestPaths};
+use traversal::{Bfs, Dfs};
+
+pub type VertexID = usize;
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct Vertex<T> {
@@ -16,7 +33,7 @@ impl<T> Vertex<T> {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Edge<Weight> {
     pub from: VertexID,
     pub to: VertexID,
@@ -40,20 +57,85 @@ impl<Weight> Edge<Weight> {
     }
 }
 
+/// Error returned when a graph operation refers to a missing vertex or edge
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphError {
+    /// there is no vertex with a given id
+    MissingVertex(VertexID),
+    /// there is no edge between two vertices
+    MissingEdge(VertexID, VertexID),
+}
+
+impl Display for GraphError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GraphError::MissingVertex(id) => write!(f, "vertex {} doesn't exist", id),
+            GraphError::MissingEdge(from, to) => {
+                write!(f, "edge between {} and {} doesn't exist", from, to)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GraphError {}
+
+/// Read-only interface shared by the graph types
+pub trait Graph {
+    type Value;
+    type Weight: Copy;
+
+    /// check if edges can be traversed in one direction only
+    fn is_directed(&self) -> bool;
+
+    /// number of vertices in the graph
+    fn len(&self) -> usize;
+
+    /// check if the graph is empty
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// check if the graph contains a vertex with a given id
+    fn contains(&self, id: VertexID) -> bool;
+
+    /// value of the vertex with a given id
+    fn value(&self, id: VertexID) -> Option<&Self::Value>;
+
+    /// iterator over ids of all vertices in the graph
+    fn vertex_ids(&self) -> impl Iterator<Item = VertexID> + '_;
+
+    /// iterator over all edges in the graph.
+    /// Every undirected edge is reported once
+    fn edges(&self) -> impl Iterator<Item = Edge<Self::Weight>> + '_;
+
+    /// number of edges in the graph
+    fn edges_count(&self) -> usize;
+
+    /// iterator over all edges which can be traversed from the vertex with a given id.
+    /// `edge.from` of every reported edge is equal to `id`
+    fn out_edges(&self, id: VertexID) -> impl Iterator<Item = Edge<Self::Weight>> + '_;
+}
+
 /// Unordered weighted graph
 pub struct WeightedGraph<T, Weight = i32> {
     vertices: HashMap<VertexID, T>,
     adjacencies: HashMap<VertexID, HashMap<VertexID, Weight>>,
 }
 
-impl<T, Weight: Copy> WeightedGraph<T, Weight> {
-    /// create a new empty graph
-    pub fn new() -> Self {
+impl<T, Weight> Default for WeightedGraph<T, Weight> {
+    fn default() -> Self {
         WeightedGraph {
             vertices: HashMap::new(),
             adjacencies: HashMap::new(),
         }
     }
+}
+
+impl<T, Weight: Copy> WeightedGraph<T, Weight> {
+    /// create a new empty graph
+    pub fn new() -> Self {
+        Self::default()
+    }
 
     /// add a new vertex to the graph
     pub fn add_vertex(&mut self, vertex: Vertex<T>) {
@@ -70,13 +152,35 @@ impl<T, Weight: Copy> WeightedGraph<T, Weight> {
         self.vertices.contains_key(&id)
     }
 
+    /// remove the vertex with a given id together with all its edges.
+    /// Returns the value of the removed vertex
+    pub fn remove_vertex(&mut self, id: VertexID) -> Option<T> {
+        let value = self.vertices.remove(&id)?;
+
+        for other_id in self.adjacencies.remove(&id).unwrap_or_default().into_keys() {
+            if let Some(links) = self.adjacencies.get_mut(&other_id) {
+                links.remove(&id);
+            }
+        }
+
+        Some(value)
+    }
+
     /// add an edge to the graph
     pub fn add_edge(&mut self, edge: Edge<Weight>) {
-        assert!(
-            self.contains(edge.from),
-            "can't connect non-existent vertex"
-        );
-        assert!(self.contains(edge.to), "can't connect non-existent vertex");
+        if let Err(error) = self.try_add_edge(edge) {
+            panic!("can't connect non-existent vertex: {}", error);
+        }
+    }
+
+    /// add an edge to the graph, failing if any of its ends doesn't exist.
+    /// An existing edge between the same vertices is replaced
+    pub fn try_add_edge(&mut self, edge: Edge<Weight>) -> Result<(), GraphError> {
+        for id in [edge.from, edge.to] {
+            if !self.contains(id) {
+                return Err(GraphError::MissingVertex(id));
+            }
+        }
 
         let id1 = edge.from;
         let id2 = edge.to;
@@ -84,6 +188,8 @@ impl<T, Weight: Copy> WeightedGraph<T, Weight> {
 
         self.adjacencies.entry(id1).or_default().insert(id2, weight);
         self.adjacencies.entry(id2).or_default().insert(id1, weight);
+
+        Ok(())
     }
 
     /// connect two vertices with an edge of a given weight
@@ -91,6 +197,58 @@ impl<T, Weight: Copy> WeightedGraph<T, Weight> {
         self.add_edge(Edge { from, to, weight })
     }
 
+    /// connect two vertices with an edge of a given weight, failing if any of them doesn't exist
+    pub fn try_connect(
+        &mut self,
+        from: VertexID,
+        to: VertexID,
+        weight: Weight,
+    ) -> Result<(), GraphError> {
+        self.try_add_edge(Edge { from, to, weight })
+    }
+
+    /// remove the edge between two vertices.
+    /// Returns the weight of the removed edge
+    pub fn remove_edge(&mut self, this: VertexID, that: VertexID) -> Option<Weight> {
+        let weight = self.adjacencies.get_mut(&this)?.remove(&that)?;
+        if let Some(links) = self.adjacencies.get_mut(&that) {
+            links.remove(&this);
+        }
+
+        Some(weight)
+    }
+
+    /// weight of the edge between two vertices
+    pub fn weight(&self, this: VertexID, that: VertexID) -> Option<Weight> {
+        self.adjacencies.get(&this)?.get(&that).copied()
+    }
+
+    /// change the weight of an existing edge.
+    /// Returns the previous weight
+    pub fn update_weight(
+        &mut self,
+        this: VertexID,
+        that: VertexID,
+        weight: Weight,
+    ) -> Result<Weight, GraphError> {
+        let previous = self
+            .adjacencies
+            .get_mut(&this)
+            .and_then(|links| links.get_mut(&that))
+            .map(|current| std::mem::replace(current, weight))
+            .ok_or(GraphError::MissingEdge(this, that))?;
+
+        if let Some(current) = self
+            .adjacencies
+            .get_mut(&that)
+            .and_then(|links| links.get_mut(&this))
+        {
+            *current = weight;
+        }
+
+        Ok(previous)
+    }
+
     /// check if two vertices are connected
     pub fn connected(&self, this: VertexID, that: VertexID) -> bool {
         let Some(links) = self.adjacencies.get(&this) else {
@@ -174,59 +332,142 @@ impl<T, Weight: Copy> WeightedGraph<T, Weight> {
     where
         Weight: Ord,
     {
-        let mut result = vec![];
-        let mut dsu = SparseUF::new();
+        mst::kruskal(self)
+    }
 
-        let edges: BinaryHeap<_> = self.edges().map(|it| std::cmp::Reverse(it)).collect();
+    /// Compute minimum spanning tree using Prim's algorithm
+    pub fn mst_prim(&self) -> Vec<Edge<Weight>>
+    where
+        Weight: Ord,
+    {
+        mst::prim(self)
+    }
 
-        for edge in edges.into_iter().map(|rev| rev.0) {
-            if result.len() == self.len() - 1 {
-                break;
-            }
+    /// Compute shortest paths from a given vertex to all reachable vertices.
+    /// Dijkstra's algorithm is used for non-negative weights, Bellman-Ford otherwise
+    pub fn shortest_paths_from(
+        &self,
+        source: VertexID,
+    ) -> Result<ShortestPaths<Weight>, NegativeCycle>
+    where
+        Weight: PathWeight,
+    {
+        shortest_paths::shortest_paths_from(self, source)
+    }
 
-            if dsu.connected(edge.from, edge.to) {
-                continue;
-            }
+    /// Compute shortest paths between all pairs of vertices using Floyd-Warshall algorithm
+    pub fn floyd_warshall(&self) -> Result<DistanceMatrix<Weight>, NegativeCycle>
+    where
+        Weight: PathWeight,
+    {
+        all_pairs::floyd_warshall(self)
+    }
 
-            dsu.join(edge.from, edge.to);
-            result.push(edge.sorted());
-        }
+    /// Compute shortest paths between all pairs of vertices using Johnson's algorithm
+    pub fn johnson(&self) -> Result<DistanceMatrix<Weight>, NegativeCycle>
+    where
+        Weight: PathWeight + Sub<Output = Weight>,
+    {
+        all_pairs::johnson(self)
+    }
 
-        result
+    /// Lazy breadth-first traversal starting at a given vertex
+    pub fn bfs(&self, start: VertexID) -> Bfs<'_, Self> {
+        Bfs::new(self, start)
     }
 
-    /// Compute minimum spanning tree using Prim's algorithm
-    pub fn mst_prim(&self) -> Vec<Edge<Weight>>
+    /// Lazy depth-first traversal starting at a given vertex
+    pub fn dfs(&self, start: VertexID) -> Dfs<'_, Self> {
+        Dfs::new(self, start)
+    }
+
+    /// Split the graph into connected components
+    pub fn connected_components(&self) -> Vec<Cluster> {
+        traversal::connected_components(self)
+    }
+
+    /// Find edges whose removal disconnects the graph
+    pub fn bridges(&self) -> Vec<Edge<Weight>> {
+        connectivity::bridges(self)
+    }
+
+    /// Find vertices whose removal disconnects the graph
+    pub fn articulation_points(&self) -> Vec<VertexID> {
+        connectivity::articulation_points(self)
+    }
+
+    /// Compute maximum flow between two vertices using Dinic's algorithm.
+    /// Edge weights are used as capacities in both directions
+    pub fn max_flow(&self, source: VertexID, sink: VertexID) -> MaxFlow<Weight>
     where
-        Weight: Ord,
+        Weight: Capacity,
     {
-        let mut result = vec![];
+        flow::dinic(self, source, sink)
+    }
+}
 
-        let mut visited_vertices = HashSet::new();
+impl<T, Weight: Copy> Graph for WeightedGraph<T, Weight> {
+    type Value = T;
+    type Weight = Weight;
 
-        let first_vertex = self.vertices().next().unwrap();
-        visited_vertices.insert(first_vertex.id);
+    fn is_directed(&self) -> bool {
+        false
+    }
 
-        let mut edges: BinaryHeap<_> = self
-            .adjacent_edges(first_vertex.id)
-            .map(|it| std::cmp::Reverse(it))
-            .collect();
+    fn len(&self) -> usize {
+        self.len()
+    }
 
-        while !edges.is_empty() {
-            let edge = edges.pop().unwrap().0;
-            if visited_vertices.contains(&edge.to) {
-                continue;
-            }
+    fn contains(&self, id: VertexID) -> bool {
+        self.contains(id)
+    }
 
-            for edge in self.adjacent_edges(edge.to) {
-                edges.push(std::cmp::Reverse(edge));
-            }
+    fn value(&self, id: VertexID) -> Option<&T> {
+        self.vertices.get(&id)
+    }
+
+    fn vertex_ids(&self) -> impl Iterator<Item = VertexID> + '_ {
+        self.vertices.keys().copied()
+    }
+
+    fn edges(&self) -> impl Iterator<Item = Edge<Weight>> + '_ {
+        self.edges()
+    }
+
+    fn edges_count(&self) -> usize {
+        self.edges_count()
+    }
 
-            visited_vertices.insert(edge.to);
-            result.push(edge.sorted());
+    fn out_edges(&self, id: VertexID) -> impl Iterator<Item = Edge<Weight>> + '_ {
+        self.adjacent_edges(id)
+    }
+}
+
+impl<T, Weight: Copy + PartialOrd> From<DirectedWeightedGraph<T, Weight>>
+    for WeightedGraph<T, Weight>
+{
+    /// forget edge directions.
+    /// If two vertices are connected in both directions, the lighter edge is kept
+    fn from(directed: DirectedWeightedGraph<T, Weight>) -> Self {
+        let (vertices, arcs) = directed.into_parts();
+        let mut graph = WeightedGraph {
+            vertices,
+            adjacencies: HashMap::new(),
+        };
+
+        for edge in arcs {
+            let lighter_exists = graph
+                .adjacencies
+                .get(&edge.from)
+                .and_then(|links| links.get(&edge.to))
+                .is_some_and(|weight| *weight <= edge.weight);
+
+            if !lighter_exists {
+                graph.add_edge(edge);
+            }
         }
 
-        result
+        graph
     }
 }
 
@@ -283,6 +524,77 @@ mod tests {
         assert!(!graph.connected(0, 2));
     }
 
+    #[test]
+    fn test_remove_vertex() {
+        let mut graph = WeightedGraph::new();
+        graph.insert(0, "A");
+        graph.insert(1, "B");
+        graph.insert(2, "C");
+        graph.connect(0, 1, 1);
+        graph.connect(1, 2, 2);
+
+        assert_eq!(graph.remove_vertex(1), Some("B"));
+        assert_eq!(graph.remove_vertex(1), None);
+        assert_eq!(graph.len(), 2);
+        assert_eq!(graph.edges_count(), 0);
+        assert_eq!(graph.adjacent_edges(0).count(), 0);
+    }
+
+    #[test]
+    fn test_remove_edge() {
+        let mut graph = WeightedGraph::new();
+        graph.insert(0, "A");
+        graph.insert(1, "B");
+        graph.connect(0, 1, 5);
+
+        assert_eq!(graph.remove_edge(1, 0), Some(5));
+        assert_eq!(graph.remove_edge(0, 1), None);
+        assert!(!graph.connected(0, 1));
+        assert!(!graph.connected(1, 0));
+        assert_eq!(graph.len(), 2);
+    }
+
+    #[test]
+    fn test_update_weight() {
+        let mut graph = WeightedGraph::new();
+        graph.insert(0, "A");
+        graph.insert(1, "B");
+        graph.insert(2, "C");
+        graph.connect(0, 1, 5);
+
+        assert_eq!(graph.update_weight(1, 0, 3), Ok(5));
+        assert_eq!(graph.weight(0, 1), Some(3));
+        assert_eq!(graph.weight(1, 0), Some(3));
+        assert_eq!(
+            graph.update_weight(0, 2, 1),
+            Err(GraphError::MissingEdge(0, 2))
+        );
+    }
+
+    #[test]
+    fn test_try_connect() {
+        let mut graph = WeightedGraph::new();
+        graph.insert(0, "A");
+
+        assert_eq!(
+            graph.try_connect(0, 1, 1),
+            Err(GraphError::MissingVertex(1))
+        );
+        assert_eq!(graph.edges_count(), 0);
+
+        graph.insert(1, "B");
+        assert_eq!(graph.try_connect(0, 1, 1), Ok(()));
+        assert!(graph.connected(1, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "can't connect non-existent vertex")]
+    fn test_add_edge_panics_on_missing_vertex() {
+        let mut graph = WeightedGraph::new();
+        graph.insert(0, "A");
+        graph.connect(0, 1, 1);
+    }
+
     #[test]
     fn test_vertices() {
         let mut graph: WeightedGraph<_, i32> = WeightedGraph::new();
@@ -0,0 +1,416 @@
+//! All-pairs shortest paths
+
+use std::{
+    collections::HashMap,
+    ops::{Index, Sub},
+};
+
+use num_traits::Zero;
+
+use super::{
+    shortest_paths::{dijkstra, relax_until_stable, NegativeCycle, PathWeight},
+    Edge, Graph, VertexID,
+};
+
+/// Shortest distances between every pair of vertices
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistanceMatrix<Weight> {
+    vertex_ids: Vec<VertexID>,
+    indices: HashMap<VertexID, usize>,
+    distances: Vec<Option<Weight>>,
+    // index of the previous vertex on the shortest path from row vertex to column vertex
+    predecessors: Vec<Option<usize>>,
+}
+
+impl<Weight: Copy> DistanceMatrix<Weight> {
+    fn new(vertex_ids: Vec<VertexID>) -> Self {
+        let n = vertex_ids.len();
+        let indices = vertex_ids
+            .iter()
+            .enumerate()
+            .map(|(idx, &id)| (id, idx))
+            .collect();
+
+        DistanceMatrix {
+            vertex_ids,
+            indices,
+            distances: vec![None; n * n],
+            predecessors: vec![None; n * n],
+        }
+    }
+
+    /// number of vertices in the matrix
+    pub fn len(&self) -> usize {
+        self.vertex_ids.len()
+    }
+
+    /// check if the matrix is empty
+    pub fn is_empty(&self) -> bool {
+        self.vertex_ids.is_empty()
+    }
+
+    /// ids of all vertices in the matrix
+    pub fn vertex_ids(&self) -> &[VertexID] {
+        &self.vertex_ids
+    }
+
+    /// length of the shortest path between two vertices or None if `to` is unreachable from `from`
+    pub fn distance(&self, from: VertexID, to: VertexID) -> Option<Weight> {
+        let from = *self.indices.get(&from)?;
+        let to = *self.indices.get(&to)?;
+
+        self.distances[self.cell(from, to)]
+    }
+
+    /// vertices of the shortest path between two vertices (both ends included)
+    pub fn path(&self, from: VertexID, to: VertexID) -> Option<Vec<VertexID>> {
+        let from_idx = *self.indices.get(&from)?;
+        let to_idx = *self.indices.get(&to)?;
+        self.distances[self.cell(from_idx, to_idx)]?;
+
+        let mut path = vec![to];
+        let mut current = to_idx;
+        while current != from_idx {
+            current = self.predecessors[self.cell(from_idx, current)]?;
+            path.push(self.vertex_ids[current]);
+        }
+        path.reverse();
+
+        Some(path)
+    }
+
+    /// distances from a given vertex to all vertices reachable from it
+    pub fn distances_from(&self, from: VertexID) -> impl Iterator<Item = (VertexID, Weight)> + '_ {
+        let row = self.indices.get(&from).copied();
+
+        row.into_iter().flat_map(move |row| {
+            self.vertex_ids
+                .iter()
+                .enumerate()
+                .filter_map(move |(col, &id)| self.distances[self.cell(row, col)].map(|d| (id, d)))
+        })
+    }
+
+    fn cell(&self, row: usize, col: usize) -> usize {
+        row * self.vertex_ids.len() + col
+    }
+}
+
+impl<Weight: Copy> Index<(VertexID, VertexID)> for DistanceMatrix<Weight> {
+    type Output = Option<Weight>;
+
+    /// distance between two vertices, panics if any of them isn't in the matrix
+    fn index(&self, (from, to): (VertexID, VertexID)) -> &Self::Output {
+        let from = self.indices[&from];
+        let to = self.indices[&to];
+
+        &self.distances[self.cell(from, to)]
+    }
+}
+
+/// Compute shortest paths between all pairs of vertices using Floyd-Warshall algorithm.
+/// Takes O(V^3) time, so it's best suited for dense graphs
+pub fn floyd_warshall<G>(graph: &G) -> Result<DistanceMatrix<G::Weight>, NegativeCycle>
+where
+    G: Graph,
+    G::Weight: PathWeight,
+{
+    let mut matrix = DistanceMatrix::new(graph.vertex_ids().collect());
+    let n = matrix.len();
+    let zero = G::Weight::zero();
+
+    for idx in 0..n {
+        let cell = matrix.cell(idx, idx);
+        matrix.distances[cell] = Some(zero);
+    }
+
+    for from_idx in 0..n {
+        for edge in graph.out_edges(matrix.vertex_ids[from_idx]) {
+            let to_idx = matrix.indices[&edge.to];
+            let cell = matrix.cell(from_idx, to_idx);
+
+            if matrix.distances[cell].is_none_or(|known| edge.weight < known) {
+                matrix.distances[cell] = Some(edge.weight);
+                matrix.predecessors[cell] = Some(from_idx);
+            }
+        }
+    }
+
+    for k in 0..n {
+        for i in 0..n {
+            let Some(i_to_k) = matrix.distances[matrix.cell(i, k)] else {
+                continue;
+            };
+
+            for j in 0..n {
+                let Some(k_to_j) = matrix.distances[matrix.cell(k, j)] else {
+                    continue;
+                };
+
+                let cell = matrix.cell(i, j);
+                let new_distance = i_to_k + k_to_j;
+                if matrix.distances[cell].is_none_or(|known| new_distance < known) {
+                    matrix.distances[cell] = Some(new_distance);
+                    matrix.predecessors[cell] = matrix.predecessors[matrix.cell(k, j)];
+                }
+            }
+        }
+    }
+
+    let has_negative_cycle =
+        (0..n).any(|idx| matrix.distances[matrix.cell(idx, idx)].is_some_and(|d| d < zero));
+    if has_negative_cycle {
+        let Err(cycle) = potentials(graph) else {
+            unreachable!("Bellman-Ford must find the same negative cycle");
+        };
+        return Err(cycle);
+    }
+
+    Ok(matrix)
+}
+
+/// Compute shortest paths between all pairs of vertices using Johnson's algorithm.
+/// Negative weights are reweighted away with Bellman-Ford, then Dijkstra's algorithm is run from every vertex,
+/// which takes O(V E log V) time and suits sparse graphs
+pub fn johnson<G>(graph: &G) -> Result<DistanceMatrix<G::Weight>, NegativeCycle>
+where
+    G: Graph,
+    G::Weight: PathWeight + Sub<Output = G::Weight>,
+{
+    let potentials = potentials(graph)?;
+    let reweighted = Reweighted {
+        graph,
+        potentials: &potentials,
+    };
+
+    let mut matrix = DistanceMatrix::new(graph.vertex_ids().collect());
+    for from_idx in 0..matrix.len() {
+        let from = matrix.vertex_ids[from_idx];
+        let paths = dijkstra(&reweighted, from);
+
+        for (&to, &distance) in paths.distances() {
+            let to_idx = matrix.indices[&to];
+            let cell = matrix.cell(from_idx, to_idx);
+
+            matrix.distances[cell] = Some(distance + potentials[&to] - potentials[&from]);
+            matrix.predecessors[cell] = paths
+                .predecessor_of(to)
+                .map(|predecessor| matrix.indices[&predecessor]);
+        }
+    }
+
+    Ok(matrix)
+}
+
+/// Vertex potentials which make every edge weight non-negative after reweighting
+fn potentials<G>(graph: &G) -> Result<HashMap<VertexID, G::Weight>, NegativeCycle>
+where
+    G: Graph,
+    G::Weight: PathWeight,
+{
+    // equivalent to starting from a virtual vertex connected to every vertex with a zero weight edge
+    let initial = graph
+        .vertex_ids()
+        .map(|id| (id, G::Weight::zero()))
+        .collect();
+
+    relax_until_stable(graph, initial).map(|(distances, _)| distances)
+}
+
+/// Graph view with edge weights shifted by vertex potentials
+struct Reweighted<'a, G: Graph> {
+    graph: &'a G,
+    potentials: &'a HashMap<VertexID, G::Weight>,
+}
+
+impl<G> Graph for Reweighted<'_, G>
+where
+    G: Graph,
+    G::Weight: PathWeight + Sub<Output = G::Weight>,
+{
+    type Value = G::Value;
+    type Weight = G::Weight;
+
+    fn is_directed(&self) -> bool {
+        self.graph.is_directed()
+    }
+
+    fn len(&self) -> usize {
+        self.graph.len()
+    }
+
+    fn contains(&self, id: VertexID) -> bool {
+        self.graph.contains(id)
+    }
+
+    fn value(&self, id: VertexID) -> Option<&G::Value> {
+        self.graph.value(id)
+    }
+
+    fn vertex_ids(&self) -> impl Iterator<Item = VertexID> + '_ {
+        self.graph.vertex_ids()
+    }
+
+    fn edges(&self) -> impl Iterator<Item = Edge<G::Weight>> + '_ {
+        self.graph.edges().map(|edge| self.reweight(edge))
+    }
+
+    fn edges_count(&self) -> usize {
+        self.graph.edges_count()
+    }
+
+    fn out_edges(&self, id: VertexID) -> impl Iterator<Item = Edge<G::Weight>> + '_ {
+        self.graph.out_edges(id).map(|edge| self.reweight(edge))
+    }
+}
+
+impl<G> Reweighted<'_, G>
+where
+    G: Graph,
+    G::Weight: PathWeight + Sub<Output = G::Weight>,
+{
+    fn reweight(&self, edge: Edge<G::Weight>) -> Edge<G::Weight> {
+        Edge {
+            weight: edge.weight + self.potentials[&edge.from] - self.potentials[&edge.to],
+            ..edge
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::{collection::vec, prop_assert_eq, proptest};
+
+    use super::*;
+    use crate::weighted_graph::{
+        shortest_paths::bellman_ford, DirectedWeightedGraph, WeightedGraph,
+    };
+
+    fn directed_graph(n: usize, edges: &[(usize, usize, i32)]) -> DirectedWeightedGraph<()> {
+        let mut graph = DirectedWeightedGraph::new();
+        for id in 0..n {
+            graph.insert(id, ());
+        }
+        for &(from, to, weight) in edges {
+            graph.connect(from, to, weight);
+        }
+
+        graph
+    }
+
+    #[rstest::fixture]
+    fn graph_with_negative_edges() -> DirectedWeightedGraph<()> {
+        directed_graph(
+            5,
+            &[
+                (0, 1, 3),
+                (0, 2, 8),
+                (0, 4, -4),
+                (1, 3, 1),
+                (1, 4, 7),
+                (2, 1, 4),
+                (3, 0, 2),
+                (3, 2, -5),
+                (4, 3, 6),
+            ],
+        )
+    }
+
+    #[rstest::rstest]
+    #[case(floyd_warshall(&graph_with_negative_edges()).unwrap())]
+    #[case(johnson(&graph_with_negative_edges()).unwrap())]
+    fn finds_all_distances(#[case] matrix: DistanceMatrix<i32>) {
+        let expected = [
+            [0, 1, -3, 2, -4],
+            [3, 0, -4, 1, -1],
+            [7, 4, 0, 5, 3],
+            [2, -1, -5, 0, -2],
+            [8, 5, 1, 6, 0],
+        ];
+
+        for (from, row) in expected.iter().enumerate() {
+            for (to, &distance) in row.iter().enumerate() {
+                assert_eq!(matrix.distance(from, to), Some(distance));
+                assert_eq!(matrix[(from, to)], Some(distance));
+            }
+        }
+
+        assert_eq!(matrix.path(0, 2), Some(vec![0, 4, 3, 2]));
+        assert_eq!(matrix.path(2, 2), Some(vec![2]));
+    }
+
+    #[rstest::rstest]
+    #[case(floyd_warshall(&directed_graph(3, &[(0, 1, 1)])).unwrap())]
+    #[case(johnson(&directed_graph(3, &[(0, 1, 1)])).unwrap())]
+    fn unreachable_pairs_have_no_distance(#[case] matrix: DistanceMatrix<i32>) {
+        assert_eq!(matrix.distance(0, 1), Some(1));
+        assert_eq!(matrix.distance(1, 0), None);
+        assert_eq!(matrix.distance(0, 2), None);
+        assert_eq!(matrix.path(1, 0), None);
+        assert_eq!(matrix.distance(0, 42), None);
+        assert_eq!(matrix.distances_from(0).count(), 2);
+    }
+
+    #[test]
+    fn negative_cycle_is_reported() {
+        let graph = directed_graph(3, &[(0, 1, 1), (1, 2, -2), (2, 1, 1)]);
+
+        assert_eq!(floyd_warshall(&graph).unwrap_err().cycle.len(), 2);
+        assert_eq!(johnson(&graph).unwrap_err().cycle.len(), 2);
+    }
+
+    #[test]
+    fn works_on_undirected_graph() {
+        let mut graph = WeightedGraph::new();
+        for id in 0..4 {
+            graph.insert(id, ());
+        }
+        graph.connect(0, 1, 1.5);
+        graph.connect(1, 2, 2.0);
+        graph.connect(0, 2, 4.0);
+
+        let matrix = graph.floyd_warshall().unwrap();
+        assert_eq!(matrix.distance(2, 0), Some(3.5));
+        assert_eq!(matrix.path(2, 0), Some(vec![2, 1, 0]));
+        assert_eq!(matrix.distance(3, 0), None);
+    }
+
+    proptest! {
+        #[test]
+        fn floyd_warshall_and_johnson_match_bellman_ford(
+            edges in vec((0..12usize, 0..12usize, 0..100i32), 0..50),
+            potentials in vec(-50..50i32, 12),
+        ) {
+            // shifting non-negative weights by potentials produces negative edges,
+            // but keeps the length of every cycle non-negative
+            let edges: Vec<_> = edges
+                .into_iter()
+                .map(|(from, to, weight)| (from, to, weight + potentials[from] - potentials[to]))
+                .collect();
+            let graph = directed_graph(12, &edges);
+
+            let floyd = floyd_warshall(&graph).unwrap();
+            let johnson = johnson(&graph).unwrap();
+
+            for from in 0..12 {
+                let expected = bellman_ford(&graph, from).unwrap();
+                for to in 0..12 {
+                    prop_assert_eq!(floyd.distance(from, to), expected.distance_to(to));
+                    prop_assert_eq!(johnson.distance(from, to), expected.distance_to(to));
+
+                    for matrix in [&floyd, &johnson] {
+                        if let Some(path) = matrix.path(from, to) {
+                            let path_len: i32 = path
+                                .windows(2)
+                                .map(|pair| {
+                                    graph.out_edges(pair[0]).find(|e| e.to == pair[1]).unwrap().weight
+                                })
+                                .sum();
+                            prop_assert_eq!(Some(path_len), expected.distance_to(to));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
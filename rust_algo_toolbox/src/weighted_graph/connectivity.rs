@@ -0,0 +1,419 @@
+//! Strongly connected components, bridges and articulation points
+
+use std::collections::{HashMap, HashSet};
+
+use super::{Edge, Graph, VertexID};
+
+type Neighbours = std::vec::IntoIter<VertexID>;
+
+fn sorted_vertex_ids<G: Graph>(graph: &G) -> Vec<VertexID> {
+    let mut ids: Vec<_> = graph.vertex_ids().collect();
+    ids.sort_unstable();
+    ids
+}
+
+fn successors<G: Graph>(graph: &G, id: VertexID) -> Neighbours {
+    let ids: Vec<_> = graph.out_edges(id).map(|edge| edge.to).collect();
+    ids.into_iter()
+}
+
+#[derive(Default)]
+struct TarjanState {
+    next_index: usize,
+    indices: HashMap<VertexID, usize>,
+    lowlinks: HashMap<VertexID, usize>,
+    stack: Vec<VertexID>,
+    on_stack: HashSet<VertexID>,
+    call_stack: Vec<(VertexID, Neighbours)>,
+}
+
+impl TarjanState {
+    fn visit<G: Graph>(&mut self, graph: &G, id: VertexID) {
+        self.indices.insert(id, self.next_index);
+        self.lowlinks.insert(id, self.next_index);
+        self.next_index += 1;
+        self.stack.push(id);
+        self.on_stack.insert(id);
+        self.call_stack.push((id, successors(graph, id)));
+    }
+
+    fn lower_lowlink(&mut self, id: VertexID, candidate: usize) {
+        let lowlink = self.lowlinks[&id].min(candidate);
+        self.lowlinks.insert(id, lowlink);
+    }
+}
+
+/// Find strongly connected components using Tarjan's algorithm.
+/// Components are returned in reverse topological order of the condensed graph
+pub fn tarjan_scc<G: Graph>(graph: &G) -> Vec<Vec<VertexID>> {
+    let mut components = vec![];
+    let mut state = TarjanState::default();
+
+    for root in sorted_vertex_ids(graph) {
+        if state.indices.contains_key(&root) {
+            continue;
+        }
+
+        state.visit(graph, root);
+
+        while let Some((id, neighbours)) = state.call_stack.last_mut() {
+            let id = *id;
+
+            if let Some(next) = neighbours.next() {
+                if !state.indices.contains_key(&next) {
+                    state.visit(graph, next);
+                } else if state.on_stack.contains(&next) {
+                    state.lower_lowlink(id, state.indices[&next]);
+                }
+                continue;
+            }
+
+            state.call_stack.pop();
+            if let Some(&(parent, _)) = state.call_stack.last() {
+                state.lower_lowlink(parent, state.lowlinks[&id]);
+            }
+
+            if state.lowlinks[&id] == state.indices[&id] {
+                let mut component = vec![];
+                loop {
+                    let member = state.stack.pop().unwrap();
+                    state.on_stack.remove(&member);
+                    component.push(member);
+                    if member == id {
+                        break;
+                    }
+                }
+                components.push(component);
+            }
+        }
+    }
+
+    components
+}
+
+/// Find strongly connected components using Kosaraju's algorithm.
+/// Components are returned in topological order of the condensed graph
+pub fn kosaraju_scc<G: Graph>(graph: &G) -> Vec<Vec<VertexID>> {
+    // first pass: order vertices by their finishing time
+    let mut visited = HashSet::new();
+    let mut finish_order = vec![];
+
+    for root in sorted_vertex_ids(graph) {
+        if !visited.insert(root) {
+            continue;
+        }
+
+        let mut call_stack = vec![(root, successors(graph, root))];
+        while let Some((id, neighbours)) = call_stack.last_mut() {
+            let id = *id;
+
+            match neighbours.next() {
+                Some(next) => {
+                    if visited.insert(next) {
+                        call_stack.push((next, successors(graph, next)));
+                    }
+                }
+                None => {
+                    call_stack.pop();
+                    finish_order.push(id);
+                }
+            }
+        }
+    }
+
+    // second pass: collect vertices reachable in the transposed graph
+    let mut transposed: HashMap<VertexID, Vec<VertexID>> = HashMap::new();
+    for &id in &finish_order {
+        for edge in graph.out_edges(id) {
+            transposed.entry(edge.to).or_default().push(edge.from);
+        }
+    }
+
+    let mut components = vec![];
+    let mut assigned = HashSet::new();
+
+    for &root in finish_order.iter().rev() {
+        if !assigned.insert(root) {
+            continue;
+        }
+
+        let mut component = vec![];
+        let mut stack = vec![root];
+        while let Some(id) = stack.pop() {
+            component.push(id);
+
+            for &next in transposed.get(&id).into_iter().flatten() {
+                if assigned.insert(next) {
+                    stack.push(next);
+                }
+            }
+        }
+
+        components.push(component);
+    }
+
+    components
+}
+
+/// Result of a low-link search over the undirected view of a graph
+struct CutStructure<Weight> {
+    bridges: Vec<Edge<Weight>>,
+    articulation_points: Vec<VertexID>,
+}
+
+/// Depth-first search computing discovery times and low-links of the undirected view of a graph.
+/// Edges are identified by their index, so that pairs of opposite directed edges are treated as parallel edges
+fn cut_structure<G: Graph>(graph: &G) -> CutStructure<G::Weight> {
+    let edges: Vec<_> = graph.edges().collect();
+
+    let mut incident: HashMap<VertexID, Vec<(VertexID, usize)>> = HashMap::new();
+    for (edge_idx, edge) in edges.iter().enumerate() {
+        if edge.from == edge.to {
+            continue;
+        }
+        incident
+            .entry(edge.from)
+            .or_default()
+            .push((edge.to, edge_idx));
+        incident
+            .entry(edge.to)
+            .or_default()
+            .push((edge.from, edge_idx));
+    }
+
+    let mut next_time = 0;
+    let mut discovery = HashMap::new();
+    let mut lowlinks = HashMap::new();
+
+    let mut bridges = vec![];
+    let mut articulation_points = vec![];
+
+    for root in sorted_vertex_ids(graph) {
+        if discovery.contains_key(&root) {
+            continue;
+        }
+
+        discovery.insert(root, next_time);
+        lowlinks.insert(root, next_time);
+        next_time += 1;
+
+        let mut root_children = 0;
+        let mut is_articulation = HashSet::new();
+
+        // (vertex, index of the edge it was entered by, position in its incidence list)
+        let mut call_stack = vec![(root, usize::MAX, 0)];
+
+        while let Some((id, parent_edge, position)) = call_stack.last_mut() {
+            let id = *id;
+            let parent_edge = *parent_edge;
+            let links = incident.get(&id).map(Vec::as_slice).unwrap_or(&[]);
+
+            if let Some(&(next, edge_idx)) = links.get(*position) {
+                *position += 1;
+
+                if edge_idx == parent_edge {
+                    continue;
+                }
+
+                if let Some(&time) = discovery.get(&next) {
+                    let lowlink = lowlinks[&id].min(time);
+                    lowlinks.insert(id, lowlink);
+                } else {
+                    discovery.insert(next, next_time);
+                    lowlinks.insert(next, next_time);
+                    next_time += 1;
+                    call_stack.push((next, edge_idx, 0));
+                }
+                continue;
+            }
+
+            call_stack.pop();
+            let Some(&(parent, _, _)) = call_stack.last() else {
+                continue;
+            };
+
+            let lowlink = lowlinks[&parent].min(lowlinks[&id]);
+            lowlinks.insert(parent, lowlink);
+
+            if lowlinks[&id] > discovery[&parent] {
+                bridges.push(edges[parent_edge]);
+            }
+
+            if parent == root {
+                root_children += 1;
+            } else if lowlinks[&id] >= discovery[&parent] {
+                is_articulation.insert(parent);
+            }
+        }
+
+        if root_children > 1 {
+            is_articulation.insert(root);
+        }
+        articulation_points.extend(is_articulation);
+    }
+
+    articulation_points.sort_unstable();
+
+    CutStructure {
+        bridges,
+        articulation_points,
+    }
+}
+
+/// Find edges whose removal increases the number of connected components.
+/// Edge directions are ignored
+pub fn bridges<G: Graph>(graph: &G) -> Vec<Edge<G::Weight>> {
+    cut_structure(graph).bridges
+}
+
+/// Find vertices whose removal increases the number of connected components.
+/// Edge directions are ignored
+pub fn articulation_points<G: Graph>(graph: &G) -> Vec<VertexID> {
+    cut_structure(graph).articulation_points
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::{collection::vec, prop_assert_eq, proptest};
+
+    use super::*;
+    use crate::weighted_graph::{DirectedWeightedGraph, WeightedGraph};
+
+    fn normalized(mut components: Vec<Vec<VertexID>>) -> Vec<Vec<VertexID>> {
+        for component in components.iter_mut() {
+            component.sort_unstable();
+        }
+        components.sort();
+        components
+    }
+
+    fn undirected_graph(n: usize, edges: &[(usize, usize)]) -> WeightedGraph<()> {
+        let mut graph = WeightedGraph::new();
+        for id in 0..n {
+            graph.insert(id, ());
+        }
+        for &(from, to) in edges {
+            graph.connect(from, to, 1);
+        }
+
+        graph
+    }
+
+    fn components_count(
+        n: usize,
+        edges: &[(usize, usize)],
+        removed_vertex: Option<usize>,
+    ) -> usize {
+        let mut graph = WeightedGraph::new();
+        for id in (0..n).filter(|&id| Some(id) != removed_vertex) {
+            graph.insert(id, ());
+        }
+        for &(from, to) in edges {
+            if graph.contains(from) && graph.contains(to) {
+                graph.connect(from, to, 1);
+            }
+        }
+
+        graph.connected_components().len()
+    }
+
+    #[test]
+    fn scc_of_two_cycles() {
+        // 0 <-> 1 -> 2 -> 3 -> 4 -> 2
+        let mut graph = DirectedWeightedGraph::new();
+        for id in 0..5 {
+            graph.insert(id, ());
+        }
+        graph.connect(0, 1, 1);
+        graph.connect(1, 0, 1);
+        graph.connect(1, 2, 1);
+        graph.connect(2, 3, 1);
+        graph.connect(3, 4, 1);
+        graph.connect(4, 2, 1);
+
+        let expected = vec![vec![0, 1], vec![2, 3, 4]];
+        assert_eq!(normalized(tarjan_scc(&graph)), expected);
+        assert_eq!(normalized(kosaraju_scc(&graph)), expected);
+
+        // reverse topological order for Tarjan, topological order for Kosaraju
+        assert!(tarjan_scc(&graph)[0].contains(&2));
+        assert!(kosaraju_scc(&graph)[0].contains(&0));
+    }
+
+    #[test]
+    fn bridges_and_articulation_points() {
+        // 0 - 1 - 2 - 0 triangle, attached to 3 - 4 by the 2 - 3 edge
+        let graph = undirected_graph(5, &[(0, 1), (1, 2), (2, 0), (2, 3), (3, 4)]);
+
+        let mut found: Vec<_> = graph
+            .bridges()
+            .into_iter()
+            .map(|edge| (edge.from.min(edge.to), edge.from.max(edge.to)))
+            .collect();
+        found.sort_unstable();
+
+        assert_eq!(found, vec![(2, 3), (3, 4)]);
+        assert_eq!(graph.articulation_points(), vec![2, 3]);
+    }
+
+    #[test]
+    fn opposite_directed_edges_arent_bridges() {
+        let mut graph = DirectedWeightedGraph::new();
+        for id in 0..3 {
+            graph.insert(id, ());
+        }
+        graph.connect(0, 1, 1);
+        graph.connect(1, 0, 1);
+        graph.connect(1, 2, 1);
+
+        let found = bridges(&graph);
+        assert_eq!(found, vec![Edge::new(1, 2, 1)]);
+        assert_eq!(articulation_points(&graph), vec![1]);
+    }
+
+    proptest! {
+        #[test]
+        fn tarjan_matches_kosaraju(edges in vec((0..15usize, 0..15usize), 0..40)) {
+            let mut graph = DirectedWeightedGraph::new();
+            for id in 0..15 {
+                graph.insert(id, ());
+            }
+            for (from, to) in edges {
+                graph.connect(from, to, 1);
+            }
+
+            prop_assert_eq!(normalized(tarjan_scc(&graph)), normalized(kosaraju_scc(&graph)));
+        }
+
+        #[test]
+        fn cut_structure_matches_brute_force(edges in vec((0..10usize, 0..10usize), 0..20)) {
+            let graph = undirected_graph(10, &edges);
+            let edges: Vec<_> = graph.edges().map(|edge| (edge.from, edge.to)).collect();
+            let components = components_count(10, &edges, None);
+
+            let mut expected_bridges = vec![];
+            for (idx, &edge) in edges.iter().enumerate() {
+                let mut rest = edges.clone();
+                rest.remove(idx);
+                if components_count(10, &rest, None) > components {
+                    expected_bridges.push(edge);
+                }
+            }
+            expected_bridges.sort_unstable();
+
+            let expected_points: Vec<_> = (0..10)
+                .filter(|&id| components_count(10, &edges, Some(id)) > components)
+                .collect();
+
+            let mut found_bridges: Vec<_> = graph
+                .bridges()
+                .into_iter()
+                .map(|edge| (edge.from, edge.to))
+                .collect();
+            found_bridges.sort_unstable();
+
+            prop_assert_eq!(found_bridges, expected_bridges);
+            prop_assert_eq!(graph.articulation_points(), expected_points);
+        }
+    }
+}
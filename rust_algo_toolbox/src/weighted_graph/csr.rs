@@ -0,0 +1,380 @@
+//! Frozen graph stored in compressed sparse row (CSR) layout
+
+use std::{collections::HashMap, ops::Range};
+
+use crate::union_find::Cluster;
+
+use super::{
+    mst,
+    shortest_paths::{self, NegativeCycle, PathWeight, ShortestPaths},
+    traversal::{self, Bfs, Dfs},
+    DirectedWeightedGraph, Edge, Graph, Vertex, VertexID, WeightedGraph,
+};
+
+/// Immutable graph with all edges packed into contiguous arrays.
+///
+/// Vertices are sorted by id and edges of every vertex are stored in a single row
+/// sorted by target id, so lookups are binary searches and traversals scan memory linearly.
+/// Undirected edges are stored in the rows of both ends.
+/// It's created by [`WeightedGraph::freeze`] or [`super::DirectedWeightedGraph::freeze`]
+pub struct CsrGraph<T, Weight = i32> {
+    directed: bool,
+    ids: Vec<VertexID>,
+    values: Vec<T>,
+    /// row of the i-th vertex is `offsets[i]..offsets[i + 1]`
+    offsets: Vec<usize>,
+    targets: Vec<VertexID>,
+    weights: Vec<Weight>,
+    edges_count: usize,
+}
+
+impl<T, Weight: Copy> CsrGraph<T, Weight> {
+    /// pack vertices and adjacency lists of a graph
+    fn from_adjacencies(
+        directed: bool,
+        vertices: HashMap<VertexID, T>,
+        mut adjacencies: HashMap<VertexID, HashMap<VertexID, Weight>>,
+    ) -> Self {
+        let mut vertices: Vec<_> = vertices.into_iter().collect();
+        vertices.sort_unstable_by_key(|(id, _)| *id);
+        let (ids, values): (Vec<_>, Vec<_>) = vertices.into_iter().unzip();
+
+        let total = adjacencies.values().map(|links| links.len()).sum();
+        let mut offsets = Vec::with_capacity(ids.len() + 1);
+        let mut targets = Vec::with_capacity(total);
+        let mut weights = Vec::with_capacity(total);
+        let mut edges_count = 0;
+
+        offsets.push(0);
+        for &id in &ids {
+            let mut links: Vec<_> = adjacencies
+                .remove(&id)
+                .unwrap_or_default()
+                .into_iter()
+                .collect();
+            links.sort_unstable_by_key(|(to, _)| *to);
+
+            for (to, weight) in links {
+                if directed || id < to {
+                    edges_count += 1;
+                }
+                targets.push(to);
+                weights.push(weight);
+            }
+            offsets.push(targets.len());
+        }
+
+        CsrGraph {
+            directed,
+            ids,
+            values,
+            offsets,
+            targets,
+            weights,
+            edges_count,
+        }
+    }
+
+    fn index_of(&self, id: VertexID) -> Option<usize> {
+        self.ids.binary_search(&id).ok()
+    }
+
+    fn row(&self, id: VertexID) -> Range<usize> {
+        match self.index_of(id) {
+            Some(index) => self.offsets[index]..self.offsets[index + 1],
+            None => 0..0,
+        }
+    }
+
+    /// check if edges can be traversed in one direction only
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    /// check if the graph contains a vertex with a given id
+    pub fn contains(&self, id: VertexID) -> bool {
+        self.index_of(id).is_some()
+    }
+
+    /// check if there is an edge going from `from` to `to`
+    pub fn connected(&self, from: VertexID, to: VertexID) -> bool {
+        self.weight(from, to).is_some()
+    }
+
+    /// weight of the edge going from `from` to `to`
+    pub fn weight(&self, from: VertexID, to: VertexID) -> Option<Weight> {
+        let row = self.row(from);
+        let position = self.targets[row.clone()].binary_search(&to).ok()?;
+
+        Some(self.weights[row.start + position])
+    }
+
+    /// number of vertices in the graph
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// check if the graph is empty
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// number of edges in the graph
+    pub fn edges_count(&self) -> usize {
+        self.edges_count
+    }
+
+    /// value of the vertex with a given id
+    pub fn value(&self, id: VertexID) -> Option<&T> {
+        self.index_of(id).map(|index| &self.values[index])
+    }
+
+    /// iterator over all vertices in the graph in order of their ids
+    pub fn vertices(&self) -> impl Iterator<Item = Vertex<&T>> {
+        self.ids
+            .iter()
+            .zip(&self.values)
+            .map(|(&id, value)| Vertex { id, value })
+    }
+
+    /// iterator over all edges in the graph.
+    /// Every undirected edge is reported once
+    pub fn edges(&self) -> impl Iterator<Item = Edge<Weight>> + '_ {
+        self.ids.iter().enumerate().flat_map(move |(index, &from)| {
+            (self.offsets[index]..self.offsets[index + 1]).filter_map(move |position| {
+                let to = self.targets[position];
+                (self.directed || from < to).then(|| Edge::new(from, to, self.weights[position]))
+            })
+        })
+    }
+
+    /// iterator over all vertices which can be reached from the vertex with a given id by one edge
+    pub fn adjacent_vertices(&self, id: VertexID) -> impl Iterator<Item = Vertex<&T>> + '_ {
+        self.adjacent_edges(id).filter_map(|edge| {
+            self.value(edge.to)
+                .map(|value| Vertex { id: edge.to, value })
+        })
+    }
+
+    /// iterator over all edges which can be traversed from the vertex with a given id
+    pub fn adjacent_edges(&self, id: VertexID) -> impl Iterator<Item = Edge<Weight>> + '_ {
+        self.row(id)
+            .map(move |position| Edge::new(id, self.targets[position], self.weights[position]))
+    }
+
+    /// number of edges which can be traversed from the vertex with a given id
+    pub fn degree(&self, id: VertexID) -> usize {
+        self.row(id).len()
+    }
+
+    /// Compute minimum spanning tree using Kruskal's algorithm
+    pub fn mst_kruskal(&self) -> Vec<Edge<Weight>>
+    where
+        Weight: Ord,
+    {
+        mst::kruskal(self)
+    }
+
+    /// Compute minimum spanning tree using Prim's algorithm
+    pub fn mst_prim(&self) -> Vec<Edge<Weight>>
+    where
+        Weight: Ord,
+    {
+        mst::prim(self)
+    }
+
+    /// Compute shortest paths from a given vertex to all reachable vertices.
+    /// Dijkstra's algorithm is used for non-negative weights, Bellman-Ford otherwise
+    pub fn shortest_paths_from(
+        &self,
+        source: VertexID,
+    ) -> Result<ShortestPaths<Weight>, NegativeCycle>
+    where
+        Weight: PathWeight,
+    {
+        shortest_paths::shortest_paths_from(self, source)
+    }
+
+    /// Lazy breadth-first traversal starting at a given vertex
+    pub fn bfs(&self, start: VertexID) -> Bfs<'_, Self> {
+        Bfs::new(self, start)
+    }
+
+    /// Lazy depth-first traversal starting at a given vertex
+    pub fn dfs(&self, start: VertexID) -> Dfs<'_, Self> {
+        Dfs::new(self, start)
+    }
+
+    /// Split the graph into (weakly) connected components
+    pub fn connected_components(&self) -> Vec<Cluster> {
+        traversal::connected_components(self)
+    }
+}
+
+impl<T, Weight: Copy> Graph for CsrGraph<T, Weight> {
+    type Value = T;
+    type Weight = Weight;
+
+    fn is_directed(&self) -> bool {
+        self.is_directed()
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn contains(&self, id: VertexID) -> bool {
+        self.contains(id)
+    }
+
+    fn value(&self, id: VertexID) -> Option<&T> {
+        self.value(id)
+    }
+
+    fn vertex_ids(&self) -> impl Iterator<Item = VertexID> + '_ {
+        self.ids.iter().copied()
+    }
+
+    fn edges(&self) -> impl Iterator<Item = Edge<Weight>> + '_ {
+        self.edges()
+    }
+
+    fn edges_count(&self) -> usize {
+        self.edges_count()
+    }
+
+    fn out_edges(&self, id: VertexID) -> impl Iterator<Item = Edge<Weight>> + '_ {
+        self.adjacent_edges(id)
+    }
+}
+
+impl<T, Weight: Copy> From<WeightedGraph<T, Weight>> for CsrGraph<T, Weight> {
+    fn from(graph: WeightedGraph<T, Weight>) -> Self {
+        CsrGraph::from_adjacencies(false, graph.vertices, graph.adjacencies)
+    }
+}
+
+impl<T, Weight: Copy> From<DirectedWeightedGraph<T, Weight>> for CsrGraph<T, Weight> {
+    fn from(graph: DirectedWeightedGraph<T, Weight>) -> Self {
+        let (vertices, out_adjacencies) = graph.into_out_adjacencies();
+
+        CsrGraph::from_adjacencies(true, vertices, out_adjacencies)
+    }
+}
+
+impl<T, Weight: Copy> WeightedGraph<T, Weight> {
+    /// Convert the graph into an immutable compact representation
+    pub fn freeze(self) -> CsrGraph<T, Weight> {
+        self.into()
+    }
+}
+
+impl<T, Weight: Copy> DirectedWeightedGraph<T, Weight> {
+    /// Convert the graph into an immutable compact representation.
+    /// Only outgoing edges are stored
+    pub fn freeze(self) -> CsrGraph<T, Weight> {
+        self.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[rstest::fixture]
+    fn graph() -> WeightedGraph<&'static str> {
+        // 3 - 1 - 7   5
+        //      \ /
+        //       0
+        let mut graph = WeightedGraph::new();
+        for (id, value) in [(3, "D"), (1, "B"), (7, "H"), (0, "A"), (5, "F")] {
+            graph.insert(id, value);
+        }
+        graph.connect(3, 1, 4);
+        graph.connect(1, 7, 2);
+        graph.connect(1, 0, 1);
+        graph.connect(0, 7, 5);
+
+        graph
+    }
+
+    #[rstest::rstest]
+    fn freeze_keeps_vertices_and_edges(graph: WeightedGraph<&'static str>) {
+        let mut expected_edges: Vec<_> = graph.edges().collect();
+        expected_edges.sort_unstable_by_key(|edge| (edge.from, edge.to));
+
+        let frozen = graph.freeze();
+        assert!(!frozen.is_directed());
+        assert_eq!(frozen.len(), 5);
+        assert_eq!(frozen.edges_count(), 4);
+
+        let vertices: Vec<_> = frozen
+            .vertices()
+            .map(|vertex| (vertex.id, *vertex.value))
+            .collect();
+        assert_eq!(
+            vertices,
+            vec![(0, "A"), (1, "B"), (3, "D"), (5, "F"), (7, "H")]
+        );
+
+        let edges: Vec<_> = frozen.edges().collect();
+        assert_eq!(edges, expected_edges);
+    }
+
+    #[rstest::rstest]
+    fn lookups(graph: WeightedGraph<&'static str>) {
+        let frozen = graph.freeze();
+
+        assert!(frozen.contains(5));
+        assert!(!frozen.contains(2));
+        assert_eq!(frozen.value(7), Some(&"H"));
+        assert!(frozen.connected(7, 1));
+        assert!(!frozen.connected(3, 7));
+        assert_eq!(frozen.weight(0, 7), Some(5));
+        assert_eq!(frozen.degree(1), 3);
+        assert_eq!(frozen.degree(5), 0);
+
+        let neighbours: Vec<_> = frozen
+            .adjacent_vertices(1)
+            .map(|vertex| vertex.id)
+            .collect();
+        assert_eq!(neighbours, vec![0, 3, 7]);
+    }
+
+    #[rstest::rstest]
+    fn algorithms_match_unfrozen_graph(graph: WeightedGraph<&'static str>) {
+        let mst_weight = |edges: Vec<Edge<i32>>| edges.iter().map(|edge| edge.weight).sum::<i32>();
+        let expected_mst = mst_weight(graph.mst_kruskal());
+        let expected_reachable: HashSet<_> = graph.bfs(3).collect();
+
+        let frozen = graph.freeze();
+        assert_eq!(mst_weight(frozen.mst_kruskal()), expected_mst);
+        assert_eq!(frozen.bfs(3).collect::<HashSet<_>>(), expected_reachable);
+        assert_eq!(frozen.dfs(3).preorder().count(), 4);
+        assert_eq!(frozen.connected_components().len(), 2);
+        assert_eq!(
+            frozen.shortest_paths_from(3).unwrap().distance_to(7),
+            Some(6)
+        );
+    }
+
+    #[test]
+    fn directed_graph_keeps_directions() {
+        let mut graph = DirectedWeightedGraph::new();
+        for id in 0..3 {
+            graph.insert(id, ());
+        }
+        graph.connect(0, 1, 1);
+        graph.connect(1, 2, 1);
+        graph.connect(2, 1, 3);
+
+        let frozen = graph.freeze();
+        assert!(frozen.is_directed());
+        assert_eq!(frozen.edges_count(), 3);
+        assert!(frozen.connected(0, 1));
+        assert!(!frozen.connected(1, 0));
+        assert_eq!(frozen.weight(2, 1), Some(3));
+        assert_eq!(frozen.bfs(1).collect::<Vec<_>>(), vec![1, 2]);
+    }
+}
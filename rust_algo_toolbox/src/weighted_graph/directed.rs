@@ -0,0 +1,535 @@
+use std::{collections::HashMap, ops::Sub};
+
+use crate::union_find::Cluster;
+
+use super::{
+    all_pairs::{self, DistanceMatrix},
+    connectivity,
+    flow::{self, Capacity, MaxFlow},
+    shortest_paths::{self, NegativeCycle, PathWeight, ShortestPaths},
+    traversal::{self, Bfs, Cycle, Dfs},
+    Edge, Graph, GraphError, Vertex, VertexID, WeightedGraph,
+};
+
+/// Directed weighted graph
+pub struct DirectedWeightedGraph<T, Weight = i32> {
+    vertices: HashMap<VertexID, T>,
+    out_adjacencies: HashMap<VertexID, HashMap<VertexID, Weight>>,
+    in_adjacencies: HashMap<VertexID, HashMap<VertexID, Weight>>,
+}
+
+impl<T, Weight> Default for DirectedWeightedGraph<T, Weight> {
+    fn default() -> Self {
+        DirectedWeightedGraph {
+            vertices: HashMap::new(),
+            out_adjacencies: HashMap::new(),
+            in_adjacencies: HashMap::new(),
+        }
+    }
+}
+
+impl<T, Weight: Copy> DirectedWeightedGraph<T, Weight> {
+    /// create a new empty graph
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// add a new vertex to the graph
+    pub fn add_vertex(&mut self, vertex: Vertex<T>) {
+        self.vertices.insert(vertex.id, vertex.value);
+    }
+
+    /// insert a new vertex into the graph
+    pub fn insert(&mut self, id: VertexID, value: T) {
+        self.add_vertex(Vertex { id, value })
+    }
+
+    /// check if the graph contains a vertex with a given id
+    pub fn contains(&self, id: VertexID) -> bool {
+        self.vertices.contains_key(&id)
+    }
+
+    /// remove the vertex with a given id together with all its incoming and outgoing edges.
+    /// Returns the value of the removed vertex
+    pub fn remove_vertex(&mut self, id: VertexID) -> Option<T> {
+        let value = self.vertices.remove(&id)?;
+
+        for to in self
+            .out_adjacencies
+            .remove(&id)
+            .unwrap_or_default()
+            .into_keys()
+        {
+            if let Some(links) = self.in_adjacencies.get_mut(&to) {
+                links.remove(&id);
+            }
+        }
+        for from in self
+            .in_adjacencies
+            .remove(&id)
+            .unwrap_or_default()
+            .into_keys()
+        {
+            if let Some(links) = self.out_adjacencies.get_mut(&from) {
+                links.remove(&id);
+            }
+        }
+
+        Some(value)
+    }
+
+    /// add an edge going from `edge.from` to `edge.to`
+    pub fn add_edge(&mut self, edge: Edge<Weight>) {
+        if let Err(error) = self.try_add_edge(edge) {
+            panic!("can't connect non-existent vertex: {}", error);
+        }
+    }
+
+    /// add an edge going from `edge.from` to `edge.to`, failing if any of its ends doesn't exist.
+    /// An existing edge between the same vertices in the same direction is replaced
+    pub fn try_add_edge(&mut self, edge: Edge<Weight>) -> Result<(), GraphError> {
+        for id in [edge.from, edge.to] {
+            if !self.contains(id) {
+                return Err(GraphError::MissingVertex(id));
+            }
+        }
+
+        self.out_adjacencies
+            .entry(edge.from)
+            .or_default()
+            .insert(edge.to, edge.weight);
+        self.in_adjacencies
+            .entry(edge.to)
+            .or_default()
+            .insert(edge.from, edge.weight);
+
+        Ok(())
+    }
+
+    /// connect two vertices with an edge of a given weight going from `from` to `to`
+    pub fn connect(&mut self, from: VertexID, to: VertexID, weight: Weight) {
+        self.add_edge(Edge { from, to, weight })
+    }
+
+    /// connect two vertices with an edge of a given weight going from `from` to `to`,
+    /// failing if any of them doesn't exist
+    pub fn try_connect(
+        &mut self,
+        from: VertexID,
+        to: VertexID,
+        weight: Weight,
+    ) -> Result<(), GraphError> {
+        self.try_add_edge(Edge { from, to, weight })
+    }
+
+    /// remove the edge going from `from` to `to`.
+    /// Returns the weight of the removed edge
+    pub fn remove_edge(&mut self, from: VertexID, to: VertexID) -> Option<Weight> {
+        let weight = self.out_adjacencies.get_mut(&from)?.remove(&to)?;
+        if let Some(links) = self.in_adjacencies.get_mut(&to) {
+            links.remove(&from);
+        }
+
+        Some(weight)
+    }
+
+    /// weight of the edge going from `from` to `to`
+    pub fn weight(&self, from: VertexID, to: VertexID) -> Option<Weight> {
+        self.out_adjacencies.get(&from)?.get(&to).copied()
+    }
+
+    /// change the weight of the existing edge going from `from` to `to`.
+    /// Returns the previous weight
+    pub fn update_weight(
+        &mut self,
+        from: VertexID,
+        to: VertexID,
+        weight: Weight,
+    ) -> Result<Weight, GraphError> {
+        let previous = self
+            .out_adjacencies
+            .get_mut(&from)
+            .and_then(|links| links.get_mut(&to))
+            .map(|current| std::mem::replace(current, weight))
+            .ok_or(GraphError::MissingEdge(from, to))?;
+
+        if let Some(current) = self
+            .in_adjacencies
+            .get_mut(&to)
+            .and_then(|links| links.get_mut(&from))
+        {
+            *current = weight;
+        }
+
+        Ok(previous)
+    }
+
+    /// check if there is an edge going from `from` to `to`
+    pub fn connected(&self, from: VertexID, to: VertexID) -> bool {
+        let Some(links) = self.out_adjacencies.get(&from) else {
+            return false;
+        };
+
+        links.contains_key(&to)
+    }
+
+    /// number of vertices in the graph
+    pub fn len(&self) -> usize {
+        self.vertices.len()
+    }
+
+    /// check if the graph is empty
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+
+    /// number of edges in the graph
+    pub fn edges_count(&self) -> usize {
+        self.out_adjacencies.values().map(|links| links.len()).sum()
+    }
+
+    /// iterator over all vertices in the graph
+    pub fn vertices(&self) -> impl Iterator<Item = Vertex<&T>> {
+        self.vertices
+            .iter()
+            .map(|(k, v)| Vertex { id: *k, value: v })
+    }
+
+    /// iterator over all edges in the graph
+    pub fn edges(&self) -> impl Iterator<Item = Edge<Weight>> + '_ {
+        self.out_adjacencies.iter().flat_map(|(&from, links)| {
+            links
+                .iter()
+                .map(move |(&to, &weight)| Edge { from, to, weight })
+        })
+    }
+
+    /// iterator over all edges starting at the vertex with a given id
+    pub fn out_edges(&self, id: VertexID) -> impl Iterator<Item = Edge<Weight>> + '_ {
+        self.out_adjacencies
+            .get(&id)
+            .into_iter()
+            .flatten()
+            .map(move |(&to, &weight)| Edge {
+                from: id,
+                to,
+                weight,
+            })
+    }
+
+    /// iterator over all edges ending at the vertex with a given id
+    pub fn in_edges(&self, id: VertexID) -> impl Iterator<Item = Edge<Weight>> + '_ {
+        self.in_adjacencies
+            .get(&id)
+            .into_iter()
+            .flatten()
+            .map(move |(&from, &weight)| Edge {
+                from,
+                to: id,
+                weight,
+            })
+    }
+
+    /// number of edges starting at the vertex with a given id
+    pub fn out_degree(&self, id: VertexID) -> usize {
+        self.out_adjacencies.get(&id).map_or(0, |links| links.len())
+    }
+
+    /// number of edges ending at the vertex with a given id
+    pub fn in_degree(&self, id: VertexID) -> usize {
+        self.in_adjacencies.get(&id).map_or(0, |links| links.len())
+    }
+
+    /// iterator over all vertices reachable from the vertex with a given id by a single edge
+    pub fn successors(&self, id: VertexID) -> impl Iterator<Item = Vertex<&T>> + '_ {
+        self.out_edges(id).filter_map(|edge| {
+            self.vertices
+                .get(&edge.to)
+                .map(|value| Vertex { id: edge.to, value })
+        })
+    }
+
+    /// iterator over all vertices from which the vertex with a given id is reachable by a single edge
+    pub fn predecessors(&self, id: VertexID) -> impl Iterator<Item = Vertex<&T>> + '_ {
+        self.in_edges(id).filter_map(|edge| {
+            self.vertices.get(&edge.from).map(|value| Vertex {
+                id: edge.from,
+                value,
+            })
+        })
+    }
+
+    /// undirected view of the graph.
+    /// If two vertices are connected in both directions, the lighter edge is kept
+    pub fn into_undirected(self) -> WeightedGraph<T, Weight>
+    where
+        Weight: PartialOrd,
+    {
+        self.into()
+    }
+
+    /// Compute shortest paths from a given vertex to all reachable vertices.
+    /// Dijkstra's algorithm is used for non-negative weights, Bellman-Ford otherwise
+    pub fn shortest_paths_from(
+        &self,
+        source: VertexID,
+    ) -> Result<ShortestPaths<Weight>, NegativeCycle>
+    where
+        Weight: PathWeight,
+    {
+        shortest_paths::shortest_paths_from(self, source)
+    }
+
+    /// Compute shortest paths between all pairs of vertices using Floyd-Warshall algorithm
+    pub fn floyd_warshall(&self) -> Result<DistanceMatrix<Weight>, NegativeCycle>
+    where
+        Weight: PathWeight,
+    {
+        all_pairs::floyd_warshall(self)
+    }
+
+    /// Compute shortest paths between all pairs of vertices using Johnson's algorithm
+    pub fn johnson(&self) -> Result<DistanceMatrix<Weight>, NegativeCycle>
+    where
+        Weight: PathWeight + Sub<Output = Weight>,
+    {
+        all_pairs::johnson(self)
+    }
+
+    /// Lazy breadth-first traversal starting at a given vertex
+    pub fn bfs(&self, start: VertexID) -> Bfs<'_, Self> {
+        Bfs::new(self, start)
+    }
+
+    /// Lazy depth-first traversal starting at a given vertex
+    pub fn dfs(&self, start: VertexID) -> Dfs<'_, Self> {
+        Dfs::new(self, start)
+    }
+
+    /// Split the graph into weakly connected components (edge directions are ignored)
+    pub fn connected_components(&self) -> Vec<Cluster> {
+        traversal::connected_components(self)
+    }
+
+    /// Find strongly connected components using Tarjan's algorithm.
+    /// Components are returned in reverse topological order of the condensed graph
+    pub fn strongly_connected_components(&self) -> Vec<Vec<VertexID>> {
+        connectivity::tarjan_scc(self)
+    }
+
+    /// Compute maximum flow between two vertices using Dinic's algorithm.
+    /// Edge weights are used as capacities
+    pub fn max_flow(&self, source: VertexID, sink: VertexID) -> MaxFlow<Weight>
+    where
+        Weight: Capacity,
+    {
+        flow::dinic(self, source, sink)
+    }
+
+    /// Order vertices so that every edge goes from an earlier vertex to a later one.
+    /// Fails with one of the cycles if the graph isn't acyclic
+    pub fn topological_sort(&self) -> Result<Vec<VertexID>, Cycle> {
+        traversal::topological_sort(self)
+    }
+
+    /// split the graph into its vertices and its edges
+    pub(super) fn into_parts(self) -> (HashMap<VertexID, T>, Vec<Edge<Weight>>) {
+        let edges = self.edges().collect();
+
+        (self.vertices, edges)
+    }
+
+    /// split the graph into its vertices and its outgoing adjacencies
+    pub(super) fn into_out_adjacencies(
+        self,
+    ) -> (
+        HashMap<VertexID, T>,
+        HashMap<VertexID, HashMap<VertexID, Weight>>,
+    ) {
+        (self.vertices, self.out_adjacencies)
+    }
+}
+
+impl<T, Weight: Copy> Graph for DirectedWeightedGraph<T, Weight> {
+    type Value = T;
+    type Weight = Weight;
+
+    fn is_directed(&self) -> bool {
+        true
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn contains(&self, id: VertexID) -> bool {
+        self.contains(id)
+    }
+
+    fn value(&self, id: VertexID) -> Option<&T> {
+        self.vertices.get(&id)
+    }
+
+    fn vertex_ids(&self) -> impl Iterator<Item = VertexID> + '_ {
+        self.vertices.keys().copied()
+    }
+
+    fn edges(&self) -> impl Iterator<Item = Edge<Weight>> + '_ {
+        self.edges()
+    }
+
+    fn edges_count(&self) -> usize {
+        self.edges_count()
+    }
+
+    fn out_edges(&self, id: VertexID) -> impl Iterator<Item = Edge<Weight>> + '_ {
+        self.out_edges(id)
+    }
+}
+
+impl<T, Weight: Copy> From<WeightedGraph<T, Weight>> for DirectedWeightedGraph<T, Weight> {
+    /// replace every undirected edge with a pair of opposite directed ones
+    fn from(undirected: WeightedGraph<T, Weight>) -> Self {
+        let edges: Vec<_> = undirected.edges().collect();
+        let mut graph = DirectedWeightedGraph {
+            vertices: undirected.vertices,
+            ..Default::default()
+        };
+
+        for edge in edges {
+            graph.connect(edge.from, edge.to, edge.weight);
+            graph.connect(edge.to, edge.from, edge.weight);
+        }
+
+        graph
+    }
+}
+
+impl<T, Weight: Copy> WeightedGraph<T, Weight> {
+    /// directed view of the graph where every edge can be traversed in both directions
+    pub fn into_directed(self) -> DirectedWeightedGraph<T, Weight> {
+        self.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[rstest::fixture]
+    fn triangle() -> DirectedWeightedGraph<&'static str> {
+        let mut graph = DirectedWeightedGraph::new();
+        graph.insert(0, "A");
+        graph.insert(1, "B");
+        graph.insert(2, "C");
+        graph.connect(0, 1, 1);
+        graph.connect(1, 2, 2);
+        graph.connect(0, 2, 3);
+        graph.connect(2, 0, 4);
+
+        graph
+    }
+
+    #[rstest::rstest]
+    fn edges_are_one_way(triangle: DirectedWeightedGraph<&'static str>) {
+        assert!(triangle.connected(0, 1));
+        assert!(!triangle.connected(1, 0));
+        assert!(triangle.connected(0, 2));
+        assert!(triangle.connected(2, 0));
+        assert_eq!(triangle.edges_count(), 4);
+    }
+
+    #[rstest::rstest]
+    fn remove_vertex_drops_its_edges(mut triangle: DirectedWeightedGraph<&'static str>) {
+        assert_eq!(triangle.remove_vertex(2), Some("C"));
+        assert_eq!(triangle.remove_vertex(2), None);
+
+        assert_eq!(triangle.len(), 2);
+        assert_eq!(triangle.edges_count(), 1);
+        assert_eq!(triangle.in_degree(0), 0);
+        assert_eq!(triangle.out_degree(1), 0);
+    }
+
+    #[rstest::rstest]
+    fn remove_and_update_edges(mut triangle: DirectedWeightedGraph<&'static str>) {
+        assert_eq!(triangle.remove_edge(1, 0), None);
+        assert_eq!(triangle.remove_edge(0, 2), Some(3));
+        assert!(!triangle.connected(0, 2));
+        assert!(triangle.connected(2, 0));
+        assert_eq!(triangle.in_degree(2), 1);
+
+        assert_eq!(triangle.update_weight(2, 0, 7), Ok(4));
+        assert_eq!(triangle.weight(2, 0), Some(7));
+        assert_eq!(triangle.in_edges(0).next(), Some(Edge::new(2, 0, 7)));
+        assert_eq!(
+            triangle.update_weight(0, 2, 1),
+            Err(GraphError::MissingEdge(0, 2))
+        );
+    }
+
+    #[rstest::rstest]
+    fn try_connect_reports_missing_vertex(mut triangle: DirectedWeightedGraph<&'static str>) {
+        assert_eq!(
+            triangle.try_connect(0, 5, 1),
+            Err(GraphError::MissingVertex(5))
+        );
+        assert_eq!(triangle.edges_count(), 4);
+        assert_eq!(triangle.try_connect(1, 0, 1), Ok(()));
+        assert!(triangle.connected(1, 0));
+    }
+
+    #[rstest::rstest]
+    fn in_and_out_edges(triangle: DirectedWeightedGraph<&'static str>) {
+        let out_edges: Vec<_> = triangle.out_edges(0).collect();
+        assert_eq!(out_edges.len(), 2);
+        assert!(out_edges.contains(&Edge::new(0, 1, 1)));
+        assert!(out_edges.contains(&Edge::new(0, 2, 3)));
+
+        let in_edges: Vec<_> = triangle.in_edges(2).collect();
+        assert_eq!(in_edges.len(), 2);
+        assert!(in_edges.contains(&Edge::new(1, 2, 2)));
+        assert!(in_edges.contains(&Edge::new(0, 2, 3)));
+    }
+
+    #[rstest::rstest]
+    fn degrees(triangle: DirectedWeightedGraph<&'static str>) {
+        assert_eq!(triangle.out_degree(0), 2);
+        assert_eq!(triangle.in_degree(0), 1);
+        assert_eq!(triangle.out_degree(1), 1);
+        assert_eq!(triangle.in_degree(1), 1);
+        assert_eq!(triangle.out_degree(2), 1);
+        assert_eq!(triangle.in_degree(2), 2);
+        assert_eq!(triangle.out_degree(3), 0);
+    }
+
+    #[rstest::rstest]
+    fn into_undirected_keeps_lighter_edge(triangle: DirectedWeightedGraph<&'static str>) {
+        let graph = triangle.into_undirected();
+        assert_eq!(graph.len(), 3);
+        assert_eq!(graph.edges_count(), 3);
+
+        let edges: Vec<_> = graph.edges().collect();
+        assert!(edges.contains(&Edge::new(0, 1, 1)));
+        assert!(edges.contains(&Edge::new(1, 2, 2)));
+        assert!(edges.contains(&Edge::new(0, 2, 3)));
+    }
+
+    #[test]
+    fn into_directed_doubles_edges() {
+        let mut graph = WeightedGraph::new();
+        graph.insert(0, "A");
+        graph.insert(1, "B");
+        graph.insert(2, "C");
+        graph.connect(0, 1, 1);
+        graph.connect(1, 2, 2);
+
+        let directed = graph.into_directed();
+        assert_eq!(directed.len(), 3);
+        assert_eq!(directed.edges_count(), 4);
+        assert!(directed.connected(0, 1));
+        assert!(directed.connected(1, 0));
+        assert!(directed.connected(1, 2));
+        assert!(directed.connected(2, 1));
+        assert!(!directed.connected(0, 2));
+    }
+}
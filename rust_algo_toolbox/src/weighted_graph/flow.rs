@@ -0,0 +1,601 @@
+//! Maximum flow, minimum cut and bipartite matching
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    ops::{Add, Sub},
+};
+
+use num_traits::Zero;
+
+use super::{DirectedWeightedGraph, Edge, Graph, VertexID};
+
+/// Edge weight which can be used as a flow capacity
+pub trait Capacity: Copy + PartialOrd + Zero + Add<Output = Self> + Sub<Output = Self> {}
+
+impl<Weight> Capacity for Weight where
+    Weight: Copy + PartialOrd + Zero + Add<Output = Self> + Sub<Output = Self>
+{
+}
+
+/// Maximum flow between two vertices
+#[derive(Debug, Clone, PartialEq)]
+pub struct MaxFlow<C> {
+    value: C,
+    flows: Vec<Edge<C>>,
+    cut: MinCut<C>,
+}
+
+impl<C: Copy> MaxFlow<C> {
+    /// total amount of flow going from the source to the sink
+    pub fn value(&self) -> C {
+        self.value
+    }
+
+    /// edges carrying a non-zero flow with `edge.weight` equal to the flow through them
+    pub fn flows(&self) -> &[Edge<C>] {
+        &self.flows
+    }
+
+    /// minimum cut separating the source from the sink
+    pub fn min_cut(&self) -> &MinCut<C> {
+        &self.cut
+    }
+}
+
+/// Minimum cut separating the source from the sink
+#[derive(Debug, Clone, PartialEq)]
+pub struct MinCut<C> {
+    /// total capacity of the cut edges, equal to the maximum flow value
+    pub value: C,
+    /// vertices which stay connected to the source after removing the cut edges
+    pub source_side: HashSet<VertexID>,
+    /// saturated edges going from the source side to the sink side
+    pub edges: Vec<Edge<C>>,
+}
+
+#[derive(Debug, Clone)]
+struct Arc<C> {
+    to: usize,
+    residual: C,
+    // index of the paired arc going in the opposite direction
+    reverse: usize,
+    // position of the graph edge this arc was created for
+    original: Option<usize>,
+}
+
+/// Residual network over densely indexed vertices
+struct FlowNetwork<C> {
+    vertex_ids: Vec<VertexID>,
+    edges: Vec<Edge<C>>,
+    arcs: Vec<Arc<C>>,
+    outgoing: Vec<Vec<usize>>,
+    source: usize,
+    sink: usize,
+}
+
+impl<C: Capacity> FlowNetwork<C> {
+    fn new<G: Graph<Weight = C>>(graph: &G, source: VertexID, sink: VertexID) -> Self {
+        assert!(graph.contains(source), "source vertex doesn't exist");
+        assert!(graph.contains(sink), "sink vertex doesn't exist");
+        assert_ne!(source, sink, "source and sink must be different vertices");
+
+        let vertex_ids: Vec<_> = graph.vertex_ids().collect();
+        let indices: HashMap<_, _> = vertex_ids
+            .iter()
+            .enumerate()
+            .map(|(idx, &id)| (id, idx))
+            .collect();
+
+        // every undirected edge is reported twice by `out_edges`, once for each direction
+        let edges: Vec<_> = vertex_ids
+            .iter()
+            .flat_map(|&id| graph.out_edges(id))
+            .filter(|edge| edge.from != edge.to)
+            .collect();
+
+        let mut network = FlowNetwork {
+            outgoing: vec![vec![]; vertex_ids.len()],
+            arcs: Vec::with_capacity(edges.len() * 2),
+            source: indices[&source],
+            sink: indices[&sink],
+            vertex_ids,
+            edges: vec![],
+        };
+
+        for (position, edge) in edges.iter().enumerate() {
+            network.add_arc(
+                indices[&edge.from],
+                indices[&edge.to],
+                edge.weight,
+                position,
+            );
+        }
+        network.edges = edges;
+
+        network
+    }
+
+    fn add_arc(&mut self, from: usize, to: usize, capacity: C, original: usize) {
+        let forward = self.arcs.len();
+        let backward = forward + 1;
+
+        self.arcs.push(Arc {
+            to,
+            residual: capacity,
+            reverse: backward,
+            original: Some(original),
+        });
+        self.arcs.push(Arc {
+            to: from,
+            residual: C::zero(),
+            reverse: forward,
+            original: None,
+        });
+
+        self.outgoing[from].push(forward);
+        self.outgoing[to].push(backward);
+    }
+
+    fn push(&mut self, arc: usize, amount: C) {
+        let reverse = self.arcs[arc].reverse;
+        self.arcs[arc].residual = self.arcs[arc].residual - amount;
+        self.arcs[reverse].residual = self.arcs[reverse].residual + amount;
+    }
+
+    /// BFS over arcs with a positive residual capacity, returns distances from the source
+    fn levels(&self) -> Vec<Option<usize>> {
+        let zero = C::zero();
+        let mut levels = vec![None; self.vertex_ids.len()];
+        levels[self.source] = Some(0);
+
+        let mut queue = VecDeque::from([self.source]);
+        while let Some(vertex) = queue.pop_front() {
+            let level = levels[vertex].unwrap();
+
+            for &arc in &self.outgoing[vertex] {
+                let Arc { to, residual, .. } = self.arcs[arc];
+                if residual > zero && levels[to].is_none() {
+                    levels[to] = Some(level + 1);
+                    queue.push_back(to);
+                }
+            }
+        }
+
+        levels
+    }
+
+    fn into_max_flow(self) -> MaxFlow<C> {
+        let zero = C::zero();
+        let levels = self.levels();
+
+        let source_side: HashSet<VertexID> = levels
+            .iter()
+            .enumerate()
+            .filter(|(_, level)| level.is_some())
+            .map(|(idx, _)| self.vertex_ids[idx])
+            .collect();
+
+        let mut flows = vec![];
+        let mut cut_edges = vec![];
+        let mut cut_value = zero;
+        let mut value = zero;
+
+        for arc in &self.arcs {
+            let Some(original) = arc.original else {
+                continue;
+            };
+
+            let edge = self.edges[original];
+            let flow = edge.weight - arc.residual;
+
+            if flow > zero {
+                flows.push(Edge {
+                    weight: flow,
+                    ..edge
+                });
+            }
+
+            if source_side.contains(&edge.from) && !source_side.contains(&edge.to) {
+                cut_value = cut_value + edge.weight;
+                cut_edges.push(edge);
+            }
+        }
+
+        let source = self.vertex_ids[self.source];
+        for edge in &flows {
+            if edge.from == source {
+                value = value + edge.weight;
+            }
+            if edge.to == source {
+                value = value - edge.weight;
+            }
+        }
+
+        MaxFlow {
+            value,
+            flows,
+            cut: MinCut {
+                value: cut_value,
+                source_side,
+                edges: cut_edges,
+            },
+        }
+    }
+}
+
+/// Compute maximum flow from `source` to `sink` using Edmonds-Karp algorithm.
+/// Edge weights are used as capacities, undirected edges can carry flow in both directions
+pub fn edmonds_karp<G>(graph: &G, source: VertexID, sink: VertexID) -> MaxFlow<G::Weight>
+where
+    G: Graph,
+    G::Weight: Capacity,
+{
+    let mut network = FlowNetwork::new(graph, source, sink);
+    let zero = G::Weight::zero();
+
+    loop {
+        // arc used to reach every vertex by the shortest augmenting path
+        let mut parent_arcs = vec![None; network.vertex_ids.len()];
+        let mut queue = VecDeque::from([network.source]);
+
+        while let Some(vertex) = queue.pop_front() {
+            if vertex == network.sink {
+                break;
+            }
+
+            for &arc in &network.outgoing[vertex] {
+                let Arc { to, residual, .. } = network.arcs[arc];
+                if residual > zero && to != network.source && parent_arcs[to].is_none() {
+                    parent_arcs[to] = Some(arc);
+                    queue.push_back(to);
+                }
+            }
+        }
+
+        if parent_arcs[network.sink].is_none() {
+            break;
+        }
+
+        let mut path = vec![];
+        let mut vertex = network.sink;
+        while let Some(arc) = parent_arcs[vertex] {
+            path.push(arc);
+            vertex = network.arcs[network.arcs[arc].reverse].to;
+        }
+
+        let mut bottleneck = network.arcs[path[0]].residual;
+        for &arc in &path[1..] {
+            if network.arcs[arc].residual < bottleneck {
+                bottleneck = network.arcs[arc].residual;
+            }
+        }
+
+        for arc in path {
+            network.push(arc, bottleneck);
+        }
+    }
+
+    network.into_max_flow()
+}
+
+/// Compute maximum flow from `source` to `sink` using Dinic's algorithm.
+/// Edge weights are used as capacities, undirected edges can carry flow in both directions
+pub fn dinic<G>(graph: &G, source: VertexID, sink: VertexID) -> MaxFlow<G::Weight>
+where
+    G: Graph,
+    G::Weight: Capacity,
+{
+    let mut network = FlowNetwork::new(graph, source, sink);
+    let zero = G::Weight::zero();
+
+    loop {
+        let levels = network.levels();
+        if levels[network.sink].is_none() {
+            break;
+        }
+
+        // position of the next arc to try for every vertex, dead ends are never revisited
+        let mut next_arc = vec![0; network.vertex_ids.len()];
+        while let Some(pushed) = blocking_flow_step(&mut network, &levels, &mut next_arc) {
+            if pushed <= zero {
+                break;
+            }
+        }
+    }
+
+    network.into_max_flow()
+}
+
+/// Push flow along a single augmenting path of the level graph and return the pushed amount.
+/// The path is grown from the source on an explicit stack of arcs. When a vertex has no arcs left,
+/// it's a dead end: the path retreats from it and the arc leading to it is skipped
+fn blocking_flow_step<C: Capacity>(
+    network: &mut FlowNetwork<C>,
+    levels: &[Option<usize>],
+    next_arc: &mut [usize],
+) -> Option<C> {
+    let zero = C::zero();
+    let mut path = vec![];
+    let mut vertex = network.source;
+
+    while vertex != network.sink {
+        let admissible = network.outgoing[vertex][next_arc[vertex]..]
+            .iter()
+            .position(|&arc| {
+                let Arc { to, residual, .. } = network.arcs[arc];
+                residual > zero && levels[to] == levels[vertex].map(|level| level + 1)
+            });
+
+        match admissible {
+            Some(skipped) => {
+                next_arc[vertex] += skipped;
+                let arc = network.outgoing[vertex][next_arc[vertex]];
+                path.push(arc);
+                vertex = network.arcs[arc].to;
+            }
+            None => {
+                next_arc[vertex] = network.outgoing[vertex].len();
+                let arc = path.pop()?;
+                vertex = network.arcs[network.arcs[arc].reverse].to;
+                next_arc[vertex] += 1;
+            }
+        }
+    }
+
+    let mut bottleneck = network.arcs[path[0]].residual;
+    for &arc in &path[1..] {
+        if network.arcs[arc].residual < bottleneck {
+            bottleneck = network.arcs[arc].residual;
+        }
+    }
+
+    for arc in path {
+        network.push(arc, bottleneck);
+    }
+
+    Some(bottleneck)
+}
+
+/// Find a maximum matching between `left` vertices and the rest of the graph.
+/// Only edges connecting a left vertex with a non-left one are considered, their weights are ignored.
+/// Returns matched pairs as `(left vertex, right vertex)`
+pub fn bipartite_matching<G: Graph>(
+    graph: &G,
+    left: &HashSet<VertexID>,
+) -> Vec<(VertexID, VertexID)> {
+    let vertex_ids: Vec<_> = graph.vertex_ids().collect();
+    let source = vertex_ids.len();
+    let sink = source + 1;
+
+    let mut network = DirectedWeightedGraph::new();
+    for idx in 0..vertex_ids.len() + 2 {
+        network.insert(idx, ());
+    }
+
+    for (idx, id) in vertex_ids.iter().enumerate() {
+        if left.contains(id) {
+            network.connect(source, idx, 1);
+        } else {
+            network.connect(idx, sink, 1);
+        }
+    }
+
+    let indices: HashMap<_, _> = vertex_ids
+        .iter()
+        .enumerate()
+        .map(|(idx, &id)| (id, idx))
+        .collect();
+
+    for edge in graph.edges() {
+        match (left.contains(&edge.from), left.contains(&edge.to)) {
+            (true, false) => network.connect(indices[&edge.from], indices[&edge.to], 1),
+            (false, true) => network.connect(indices[&edge.to], indices[&edge.from], 1),
+            _ => {}
+        }
+    }
+
+    dinic(&network, source, sink)
+        .flows()
+        .iter()
+        .filter(|edge| edge.from != source && edge.to != sink)
+        .map(|edge| (vertex_ids[edge.from], vertex_ids[edge.to]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::{collection::vec, prop_assert, prop_assert_eq, proptest};
+
+    use super::*;
+    use crate::weighted_graph::WeightedGraph;
+
+    /// minimum over all cuts separating vertex 0 from vertex `n - 1`
+    fn brute_force_min_cut<G>(graph: &G, n: usize) -> G::Weight
+    where
+        G: Graph,
+        G::Weight: Capacity,
+    {
+        let mut best: Option<G::Weight> = None;
+
+        for mask in 0..(1u32 << n) {
+            let in_source_side = |id: usize| mask & (1 << id) != 0;
+            if !in_source_side(0) || in_source_side(n - 1) {
+                continue;
+            }
+
+            let mut value = G::Weight::zero();
+            for id in 0..n {
+                for edge in graph.out_edges(id) {
+                    if in_source_side(edge.from) && !in_source_side(edge.to) {
+                        value = value + edge.weight;
+                    }
+                }
+            }
+
+            if best.is_none_or(|best| value < best) {
+                best = Some(value);
+            }
+        }
+
+        best.unwrap()
+    }
+
+    #[rstest::fixture]
+    fn network() -> DirectedWeightedGraph<()> {
+        // classic CLRS example with the maximum flow of 23
+        let mut graph = DirectedWeightedGraph::new();
+        for id in 0..6 {
+            graph.insert(id, ());
+        }
+        graph.connect(0, 1, 16);
+        graph.connect(0, 2, 13);
+        graph.connect(1, 3, 12);
+        graph.connect(2, 1, 4);
+        graph.connect(2, 4, 14);
+        graph.connect(3, 2, 9);
+        graph.connect(3, 5, 20);
+        graph.connect(4, 3, 7);
+        graph.connect(4, 5, 4);
+
+        graph
+    }
+
+    #[rstest::rstest]
+    #[case(edmonds_karp(&network(), 0, 5))]
+    #[case(dinic(&network(), 0, 5))]
+    fn finds_max_flow(#[case] flow: MaxFlow<i32>) {
+        assert_eq!(flow.value(), 23);
+
+        let cut = flow.min_cut();
+        assert_eq!(cut.value, 23);
+        assert_eq!(cut.source_side, HashSet::from([0, 1, 2, 4]));
+
+        let mut cut_edges: Vec<_> = cut.edges.iter().map(|e| (e.from, e.to)).collect();
+        cut_edges.sort_unstable();
+        assert_eq!(cut_edges, vec![(1, 3), (4, 3), (4, 5)]);
+    }
+
+    #[test]
+    fn flow_is_conserved() {
+        let graph = network();
+        let flow = dinic(&graph, 0, 5);
+
+        for id in 1..5 {
+            let incoming: i32 = flow
+                .flows()
+                .iter()
+                .filter(|e| e.to == id)
+                .map(|e| e.weight)
+                .sum();
+            let outgoing: i32 = flow
+                .flows()
+                .iter()
+                .filter(|e| e.from == id)
+                .map(|e| e.weight)
+                .sum();
+            assert_eq!(incoming, outgoing);
+        }
+
+        for edge in flow.flows() {
+            assert!(
+                edge.weight
+                    <= graph
+                        .out_edges(edge.from)
+                        .find(|e| e.to == edge.to)
+                        .unwrap()
+                        .weight
+            );
+        }
+    }
+
+    #[test]
+    fn undirected_edges_carry_flow_both_ways() {
+        let mut graph = WeightedGraph::new();
+        for id in 0..4 {
+            graph.insert(id, ());
+        }
+        graph.connect(0, 1, 1.5);
+        graph.connect(0, 2, 2.0);
+        graph.connect(1, 2, 1.0);
+        graph.connect(1, 3, 3.0);
+        graph.connect(2, 3, 0.5);
+
+        assert_eq!(graph.max_flow(0, 3).value(), 3.0);
+        assert_eq!(graph.max_flow(3, 0).value(), 3.0);
+    }
+
+    #[test]
+    fn dinic_handles_long_level_graphs() {
+        let n = 100_000;
+        let mut graph = DirectedWeightedGraph::new();
+        for id in 0..n {
+            graph.insert(id, ());
+        }
+        for id in 1..n {
+            graph.connect(id - 1, id, 1 + id as i32 % 3);
+        }
+
+        assert_eq!(dinic(&graph, 0, n - 1).value(), 1);
+    }
+
+    #[test]
+    fn matching_is_maximum() {
+        // left: 0, 1, 2; right: 3, 4, 5
+        let mut graph = WeightedGraph::new();
+        for id in 0..6 {
+            graph.insert(id, ());
+        }
+        graph.connect(0, 3, 1);
+        graph.connect(0, 4, 1);
+        graph.connect(1, 3, 1);
+        graph.connect(2, 3, 1);
+        graph.connect(2, 5, 1);
+
+        let left = HashSet::from([0, 1, 2]);
+        let mut matching = bipartite_matching(&graph, &left);
+        matching.sort_unstable();
+
+        assert_eq!(matching, vec![(0, 4), (1, 3), (2, 5)]);
+    }
+
+    proptest! {
+        #[test]
+        fn max_flow_matches_brute_force_min_cut(
+            edges in vec((0..7usize, 0..7usize, 0..20i32), 0..25),
+        ) {
+            let mut graph = DirectedWeightedGraph::new();
+            for id in 0..7 {
+                graph.insert(id, ());
+            }
+            for (from, to, capacity) in edges {
+                graph.connect(from, to, capacity);
+            }
+
+            let expected = brute_force_min_cut(&graph, 7);
+            let edmonds_karp = edmonds_karp(&graph, 0, 6);
+            let dinic = dinic(&graph, 0, 6);
+
+            prop_assert_eq!(edmonds_karp.value(), expected);
+            prop_assert_eq!(dinic.value(), expected);
+            prop_assert_eq!(edmonds_karp.min_cut().value, expected);
+            prop_assert_eq!(dinic.min_cut().value, expected);
+        }
+
+        #[test]
+        fn float_max_flow_matches_brute_force_min_cut(
+            edges in vec((0..6usize, 0..6usize, 0.0..10.0f64), 0..20),
+        ) {
+            let mut graph = WeightedGraph::new();
+            for id in 0..6 {
+                graph.insert(id, ());
+            }
+            for (from, to, capacity) in edges {
+                graph.connect(from, to, capacity);
+            }
+
+            let expected = brute_force_min_cut(&graph, 6);
+            prop_assert!((edmonds_karp(&graph, 0, 5).value() - expected).abs() < 1e-9);
+            prop_assert!((dinic(&graph, 0, 5).value() - expected).abs() < 1e-9);
+        }
+    }
+}
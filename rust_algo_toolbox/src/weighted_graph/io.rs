@@ -0,0 +1,523 @@
+//! Reading and writing graphs in text formats
+//!
+//! * weighted edge list: one `from to weight` edge per line,
+//!   a line with a single id declares an isolated vertex, `#` starts a comment
+//! * DIMACS shortest-path format: `p sp <vertices> <arcs>` header followed by `a <from> <to> <weight>` arcs,
+//!   `c` lines are comments and vertices are numbered from 1
+//! * Graphviz DOT (write only)
+
+use std::{
+    collections::HashSet,
+    fmt::Display,
+    io::{BufRead, Write},
+    str::FromStr,
+};
+
+use super::{DirectedWeightedGraph, Edge, Graph, VertexID, WeightedGraph};
+
+/// Error returned when a graph can't be read
+#[derive(Debug)]
+pub enum ReadError {
+    /// the underlying reader failed
+    Io(std::io::Error),
+    /// the input is malformed
+    Parse {
+        /// 1-based number of the offending line
+        line: usize,
+        message: String,
+    },
+}
+
+impl Display for ReadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReadError::Io(error) => write!(f, "failed to read graph: {}", error),
+            ReadError::Parse { line, message } => write!(f, "line {}: {}", line, message),
+        }
+    }
+}
+
+impl std::error::Error for ReadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ReadError::Io(error) => Some(error),
+            ReadError::Parse { .. } => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for ReadError {
+    fn from(error: std::io::Error) -> Self {
+        ReadError::Io(error)
+    }
+}
+
+/// the largest vertices count accepted in a DIMACS problem line.
+/// All vertices, isolated ones included, are allocated from the header alone,
+/// so the limit bounds the memory a one-line input can make the reader allocate
+pub const MAX_DIMACS_VERTICES: usize = 1 << 20;
+
+/// vertices and edges read from a file
+struct Parsed<Weight> {
+    vertices: Vec<VertexID>,
+    edges: Vec<Edge<Weight>>,
+}
+
+fn parse_error(line: usize, message: String) -> ReadError {
+    ReadError::Parse { line, message }
+}
+
+fn parse_token<V: FromStr>(line: usize, token: &str, what: &str) -> Result<V, ReadError> {
+    token
+        .parse()
+        .map_err(|_| parse_error(line, format!("invalid {} {:?}", what, token)))
+}
+
+fn parse_edge_list<Weight: FromStr, R: BufRead>(reader: R) -> Result<Parsed<Weight>, ReadError> {
+    let mut parsed = Parsed {
+        vertices: vec![],
+        edges: vec![],
+    };
+
+    for (index, line) in reader.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line?;
+        let content = line.split('#').next().unwrap_or_default();
+        let tokens: Vec<_> = content.split_whitespace().collect();
+
+        match tokens[..] {
+            [] => {}
+            [id] => parsed
+                .vertices
+                .push(parse_token(line_number, id, "vertex id")?),
+            [from, to, weight] => {
+                let from = parse_token(line_number, from, "vertex id")?;
+                let to = parse_token(line_number, to, "vertex id")?;
+                let weight = parse_token(line_number, weight, "weight")?;
+
+                parsed.vertices.extend([from, to]);
+                parsed.edges.push(Edge { from, to, weight });
+            }
+            _ => {
+                return Err(parse_error(
+                    line_number,
+                    format!("expected `from to weight`, got {:?}", content.trim()),
+                ))
+            }
+        }
+    }
+
+    Ok(parsed)
+}
+
+fn parse_dimacs<Weight: FromStr, R: BufRead>(reader: R) -> Result<Parsed<Weight>, ReadError> {
+    let mut header: Option<(usize, usize)> = None;
+    let mut edges = vec![];
+    let mut last_line = 0;
+
+    for (index, line) in reader.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line?;
+        last_line = line_number;
+        let tokens: Vec<_> = line.split_whitespace().collect();
+
+        match tokens[..] {
+            [] | ["c", ..] => {}
+            ["p", "sp", vertices, arcs] => {
+                if header.is_some() {
+                    return Err(parse_error(line_number, "duplicate problem line".into()));
+                }
+                let vertices = parse_token(line_number, vertices, "vertices count")?;
+                if vertices > MAX_DIMACS_VERTICES {
+                    return Err(parse_error(
+                        line_number,
+                        format!(
+                            "vertices count {} exceeds the limit of {}",
+                            vertices, MAX_DIMACS_VERTICES
+                        ),
+                    ));
+                }
+                header = Some((vertices, parse_token(line_number, arcs, "arcs count")?));
+            }
+            ["a", from, to, weight] => {
+                let Some((vertices, _)) = header else {
+                    return Err(parse_error(line_number, "arc before problem line".into()));
+                };
+
+                let parse_id = |token| {
+                    let id: VertexID = parse_token(line_number, token, "vertex id")?;
+                    if id == 0 || id > vertices {
+                        return Err(parse_error(
+                            line_number,
+                            format!("vertex id {} is out of range 1..={}", id, vertices),
+                        ));
+                    }
+                    Ok(id - 1)
+                };
+                let from = parse_id(from)?;
+                let to = parse_id(to)?;
+                let weight = parse_token(line_number, weight, "weight")?;
+
+                edges.push(Edge { from, to, weight });
+            }
+            _ => {
+                return Err(parse_error(
+                    line_number,
+                    format!("unexpected line {:?}", line.trim()),
+                ))
+            }
+        }
+    }
+
+    let Some((vertices, arcs)) = header else {
+        return Err(parse_error(last_line, "missing problem line".into()));
+    };
+    if edges.len() != arcs {
+        return Err(parse_error(
+            last_line,
+            format!("expected {} arcs, got {}", arcs, edges.len()),
+        ));
+    }
+
+    Ok(Parsed {
+        vertices: (0..vertices).collect(),
+        edges,
+    })
+}
+
+fn sorted_vertex_ids<G: Graph>(graph: &G) -> Vec<VertexID> {
+    let mut ids: Vec<_> = graph.vertex_ids().collect();
+    ids.sort_unstable();
+    ids
+}
+
+fn sorted_edges<G: Graph>(graph: &G) -> Vec<Edge<G::Weight>> {
+    let mut edges: Vec<_> = graph.edges().collect();
+    edges.sort_unstable_by_key(|edge| (edge.from, edge.to));
+    edges
+}
+
+/// Write the graph as a weighted edge list.
+/// Vertices without edges are written as lines with a single id
+pub fn write_edge_list<G, W>(graph: &G, mut writer: W) -> std::io::Result<()>
+where
+    G: Graph,
+    G::Weight: Display,
+    W: Write,
+{
+    let edges = sorted_edges(graph);
+    let connected: HashSet<_> = edges.iter().flat_map(|edge| [edge.from, edge.to]).collect();
+
+    for id in sorted_vertex_ids(graph) {
+        if !connected.contains(&id) {
+            writeln!(writer, "{}", id)?;
+        }
+    }
+
+    for edge in edges {
+        writeln!(writer, "{} {} {}", edge.from, edge.to, edge.weight)?;
+    }
+
+    Ok(())
+}
+
+/// Write the graph in DIMACS shortest-path format.
+/// Vertex `id` is written as `id + 1` and every undirected edge is written as two arcs
+pub fn write_dimacs<G, W>(graph: &G, mut writer: W) -> std::io::Result<()>
+where
+    G: Graph,
+    G::Weight: Display,
+    W: Write,
+{
+    let vertices = graph.vertex_ids().max().map_or(0, |id| id + 1);
+    let mut arcs = sorted_edges(graph);
+    if !graph.is_directed() {
+        let reversed: Vec<_> = arcs
+            .iter()
+            .filter(|edge| edge.from != edge.to)
+            .map(|edge| Edge::new(edge.to, edge.from, edge.weight))
+            .collect();
+        arcs.extend(reversed);
+        arcs.sort_unstable_by_key(|edge| (edge.from, edge.to));
+    }
+
+    writeln!(writer, "p sp {} {}", vertices, arcs.len())?;
+    for arc in arcs {
+        writeln!(writer, "a {} {} {}", arc.from + 1, arc.to + 1, arc.weight)?;
+    }
+
+    Ok(())
+}
+
+/// Write the graph in Graphviz DOT format.
+/// Edges contained in `highlighted` (e.g. a minimum spanning tree) are drawn in red
+pub fn write_dot<G, W>(
+    graph: &G,
+    mut writer: W,
+    highlighted: &[Edge<G::Weight>],
+) -> std::io::Result<()>
+where
+    G: Graph,
+    G::Weight: Display,
+    W: Write,
+{
+    let directed = graph.is_directed();
+    let key = |edge: &Edge<G::Weight>| {
+        if directed {
+            (edge.from, edge.to)
+        } else {
+            (edge.from.min(edge.to), edge.from.max(edge.to))
+        }
+    };
+    let highlighted: HashSet<_> = highlighted.iter().map(key).collect();
+
+    let (kind, arrow) = if directed {
+        ("digraph", "->")
+    } else {
+        ("graph", "--")
+    };
+
+    writeln!(writer, "{} G {{", kind)?;
+
+    for id in sorted_vertex_ids(graph) {
+        writeln!(writer, "  N{}[label=\"{}\"];", id, id)?;
+    }
+
+    for edge in sorted_edges(graph) {
+        write!(
+            writer,
+            "  N{} {} N{} [label=\"{}\"",
+            edge.from, arrow, edge.to, edge.weight
+        )?;
+        if highlighted.contains(&key(&edge)) {
+            write!(writer, "; color=\"red\"; penwidth=2")?;
+        }
+        writeln!(writer, "];")?;
+    }
+
+    writeln!(writer, "}}")?;
+
+    Ok(())
+}
+
+impl<Weight: Copy + FromStr> WeightedGraph<(), Weight> {
+    /// Read a graph from a weighted edge list
+    pub fn read_edge_list<R: BufRead>(reader: R) -> Result<Self, ReadError> {
+        parse_edge_list(reader).map(Self::from_parsed)
+    }
+
+    /// Read a graph in DIMACS shortest-path format.
+    /// Vertex `n` of the file gets id `n - 1`
+    pub fn read_dimacs<R: BufRead>(reader: R) -> Result<Self, ReadError> {
+        parse_dimacs(reader).map(Self::from_parsed)
+    }
+
+    fn from_parsed(parsed: Parsed<Weight>) -> Self {
+        let mut graph = WeightedGraph::new();
+        for id in parsed.vertices {
+            graph.insert(id, ());
+        }
+        for edge in parsed.edges {
+            graph.add_edge(edge);
+        }
+
+        graph
+    }
+}
+
+impl<Weight: Copy + FromStr> DirectedWeightedGraph<(), Weight> {
+    /// Read a graph from a weighted edge list
+    pub fn read_edge_list<R: BufRead>(reader: R) -> Result<Self, ReadError> {
+        parse_edge_list(reader).map(Self::from_parsed)
+    }
+
+    /// Read a graph in DIMACS shortest-path format.
+    /// Vertex `n` of the file gets id `n - 1`
+    pub fn read_dimacs<R: BufRead>(reader: R) -> Result<Self, ReadError> {
+        parse_dimacs(reader).map(Self::from_parsed)
+    }
+
+    fn from_parsed(parsed: Parsed<Weight>) -> Self {
+        let mut graph = DirectedWeightedGraph::new();
+        for id in parsed.vertices {
+            graph.insert(id, ());
+        }
+        for edge in parsed.edges {
+            graph.add_edge(edge);
+        }
+
+        graph
+    }
+}
+
+impl<T, Weight: Copy + Display> WeightedGraph<T, Weight> {
+    /// Write the graph as a weighted edge list
+    pub fn write_edge_list<W: Write>(&self, writer: W) -> std::io::Result<()> {
+        write_edge_list(self, writer)
+    }
+
+    /// Write the graph in DIMACS shortest-path format
+    pub fn write_dimacs<W: Write>(&self, writer: W) -> std::io::Result<()> {
+        write_dimacs(self, writer)
+    }
+
+    /// Write the graph in Graphviz DOT format highlighting a given set of edges
+    pub fn generate_dot<W: Write>(
+        &self,
+        writer: W,
+        highlighted: &[Edge<Weight>],
+    ) -> std::io::Result<()> {
+        write_dot(self, writer, highlighted)
+    }
+}
+
+impl<T, Weight: Copy + Display> DirectedWeightedGraph<T, Weight> {
+    /// Write the graph as a weighted edge list
+    pub fn write_edge_list<W: Write>(&self, writer: W) -> std::io::Result<()> {
+        write_edge_list(self, writer)
+    }
+
+    /// Write the graph in DIMACS shortest-path format
+    pub fn write_dimacs<W: Write>(&self, writer: W) -> std::io::Result<()> {
+        write_dimacs(self, writer)
+    }
+
+    /// Write the graph in Graphviz DOT format highlighting a given set of edges
+    pub fn generate_dot<W: Write>(
+        &self,
+        writer: W,
+        highlighted: &[Edge<Weight>],
+    ) -> std::io::Result<()> {
+        write_dot(self, writer, highlighted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[rstest::fixture]
+    fn graph() -> WeightedGraph<(), i32> {
+        let mut graph = WeightedGraph::new();
+        for id in 0..4 {
+            graph.insert(id, ());
+        }
+        graph.connect(0, 1, 3);
+        graph.connect(1, 2, -1);
+        graph.connect(2, 0, 7);
+
+        graph
+    }
+
+    fn to_string(write: impl FnOnce(&mut Vec<u8>) -> std::io::Result<()>) -> String {
+        let mut buffer = vec![];
+        write(&mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[rstest::rstest]
+    fn edge_list_roundtrip(graph: WeightedGraph<(), i32>) {
+        let text = to_string(|buffer| graph.write_edge_list(buffer));
+        assert_eq!(text, "3\n0 1 3\n0 2 7\n1 2 -1\n");
+
+        let read = WeightedGraph::<(), i32>::read_edge_list(text.as_bytes()).unwrap();
+        assert_eq!(read.len(), 4);
+        assert_eq!(sorted_edges(&read), sorted_edges(&graph));
+    }
+
+    #[test]
+    fn edge_list_skips_comments_and_blank_lines() {
+        let text = "# triangle\n\n0 1 2.5  # first edge\n1 2 0.5\n";
+        let graph = DirectedWeightedGraph::<(), f64>::read_edge_list(text.as_bytes()).unwrap();
+
+        assert_eq!(graph.len(), 3);
+        assert_eq!(
+            sorted_edges(&graph),
+            vec![Edge::new(0, 1, 2.5), Edge::new(1, 2, 0.5)]
+        );
+    }
+
+    #[rstest::rstest]
+    #[case("0 1\n", 1)]
+    #[case("0 1 2\n0 x 2\n", 2)]
+    #[case("0 1 heavy\n", 1)]
+    fn edge_list_reports_malformed_lines(#[case] text: &str, #[case] expected_line: usize) {
+        let result = WeightedGraph::<(), i32>::read_edge_list(text.as_bytes());
+        assert!(matches!(result, Err(ReadError::Parse { line, .. }) if line == expected_line));
+    }
+
+    #[test]
+    fn dimacs_roundtrip() {
+        let text = "c small example\np sp 3 3\na 1 2 4\na 2 3 5\na 3 1 6\n";
+        let graph = DirectedWeightedGraph::<(), u32>::read_dimacs(text.as_bytes()).unwrap();
+
+        assert_eq!(graph.len(), 3);
+        assert_eq!(
+            sorted_edges(&graph),
+            vec![Edge::new(0, 1, 4), Edge::new(1, 2, 5), Edge::new(2, 0, 6)]
+        );
+
+        let written = to_string(|buffer| graph.write_dimacs(buffer));
+        assert_eq!(written, "p sp 3 3\na 1 2 4\na 2 3 5\na 3 1 6\n");
+    }
+
+    #[rstest::rstest]
+    fn undirected_dimacs_has_arcs_in_both_directions(graph: WeightedGraph<(), i32>) {
+        let text = to_string(|buffer| graph.write_dimacs(buffer));
+        assert!(text.starts_with("p sp 4 6\n"));
+
+        let read = WeightedGraph::<(), i32>::read_dimacs(text.as_bytes()).unwrap();
+        assert_eq!(read.len(), 4);
+        assert_eq!(sorted_edges(&read), sorted_edges(&graph));
+    }
+
+    #[rstest::rstest]
+    #[case("a 1 2 3\n")]
+    #[case("p sp 2 1\na 1 3 3\n")]
+    #[case("p sp 2 2\na 1 2 3\n")]
+    #[case("p sp 2 1\np sp 2 1\na 1 2 3\n")]
+    #[case("p max 2 1\na 1 2 3\n")]
+    #[case("p sp 18446744073709551615 0\n")]
+    fn dimacs_rejects_malformed_input(#[case] text: &str) {
+        let result = DirectedWeightedGraph::<(), i32>::read_dimacs(text.as_bytes());
+        assert!(matches!(result, Err(ReadError::Parse { .. })));
+    }
+
+    #[test]
+    fn dimacs_rejects_oversized_header_before_reading_arcs() {
+        let text = format!("p sp {} 1\na 1 2 3\n", MAX_DIMACS_VERTICES + 1);
+
+        let result = DirectedWeightedGraph::<(), i32>::read_dimacs(text.as_bytes());
+        assert!(
+            matches!(result, Err(ReadError::Parse { line: 1, message }) if message.contains("exceeds"))
+        );
+    }
+
+    #[rstest::rstest]
+    fn dot_highlights_edges(graph: WeightedGraph<(), i32>) {
+        let mst = graph.mst_kruskal();
+        let text = to_string(|buffer| graph.generate_dot(buffer, &mst));
+
+        assert_eq!(
+            text,
+            "graph G {\n  N0[label=\"0\"];\n  N1[label=\"1\"];\n  N2[label=\"2\"];\n  N3[label=\"3\"];\n  \
+             N0 -- N1 [label=\"3\"; color=\"red\"; penwidth=2];\n  \
+             N0 -- N2 [label=\"7\"];\n  \
+             N1 -- N2 [label=\"-1\"; color=\"red\"; penwidth=2];\n}\n"
+        );
+    }
+
+    #[test]
+    fn directed_dot_uses_arrows() {
+        let mut graph = DirectedWeightedGraph::new();
+        graph.insert(0, ());
+        graph.insert(1, ());
+        graph.connect(1, 0, 2);
+
+        let text = to_string(|buffer| graph.generate_dot(buffer, &[Edge::new(0, 1, 2)]));
+        assert_eq!(
+            text,
+            "digraph G {\n  N0[label=\"0\"];\n  N1[label=\"1\"];\n  N1 -> N0 [label=\"2\"];\n}\n"
+        );
+    }
+}
@@ -0,0 +1,127 @@
+//! Minimum spanning trees of undirected graphs
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    pqueue::indexed_heap::IndexedMinHeap,
+    union_find::{SparseUF, UnionFind},
+};
+
+use super::{Edge, Graph};
+
+/// Compute minimum spanning tree (or forest) using Kruskal's algorithm.
+/// Edges of the tree are returned with sorted ends
+pub fn kruskal<G>(graph: &G) -> Vec<Edge<G::Weight>>
+where
+    G: Graph,
+    G::Weight: Ord,
+{
+    let mut result = vec![];
+    let mut dsu = SparseUF::new();
+
+    let mut edges: Vec<_> = graph.edges().collect();
+    edges.sort_unstable();
+
+    for edge in edges {
+        if result.len() + 1 >= graph.len() {
+            break;
+        }
+
+        if dsu.connected(edge.from, edge.to) {
+            continue;
+        }
+
+        dsu.join(edge.from, edge.to);
+        result.push(edge.sorted());
+    }
+
+    result
+}
+
+/// Compute minimum spanning tree of the component containing an arbitrary vertex
+/// using Prim's algorithm. Edges of the tree are returned with sorted ends
+pub fn prim<G>(graph: &G) -> Vec<Edge<G::Weight>>
+where
+    G: Graph,
+    G::Weight: Ord,
+{
+    let mut result = vec![];
+
+    let Some(first_vertex) = graph.vertex_ids().next() else {
+        return result;
+    };
+
+    let mut visited_vertices = HashSet::new();
+    // lightest known edge connecting every reached vertex to the tree
+    let mut edges = IndexedMinHeap::<_>::new();
+    let mut handles = HashMap::new();
+
+    let mut next = Some(first_vertex);
+    while let Some(id) = next {
+        visited_vertices.insert(id);
+
+        for edge in graph.out_edges(id) {
+            if visited_vertices.contains(&edge.to) {
+                continue;
+            }
+
+            match handles.get(&edge.to) {
+                Some(&handle) => {
+                    if edges.get(handle).is_some_and(|known| edge < *known) {
+                        edges.change_priority(handle, edge);
+                    }
+                }
+                None => {
+                    handles.insert(edge.to, edges.push(edge));
+                }
+            }
+        }
+
+        next = edges.pop().map(|(_, edge)| {
+            result.push(edge.sorted());
+            edge.to
+        });
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weighted_graph::WeightedGraph;
+    use proptest::prelude::*;
+
+    fn total_weight(edges: &[Edge<u32>]) -> u32 {
+        edges.iter().map(|edge| edge.weight).sum()
+    }
+
+    proptest! {
+        #[test]
+        fn kruskal_and_prim_agree_on_connected_graphs(
+            weights in prop::collection::vec(0u32..100, 1..40),
+            extra in prop::collection::vec((0usize..12, 0usize..12, 0u32..100), 0..40),
+        ) {
+            // a path through all vertices keeps the graph connected
+            let mut graph = WeightedGraph::new();
+            for id in 0..=weights.len() {
+                graph.insert(id, ());
+            }
+            for (id, &weight) in weights.iter().enumerate() {
+                graph.connect(id, id + 1, weight);
+            }
+            for (from, to, weight) in extra {
+                if from != to && from <= weights.len() && to <= weights.len() {
+                    graph.connect(from, to, weight);
+                }
+            }
+
+            let by_kruskal = kruskal(&graph);
+            let by_prim = prim(&graph);
+
+            prop_assert_eq!(by_kruskal.len(), graph.len() - 1);
+            prop_assert_eq!(by_prim.len(), graph.len() - 1);
+            prop_assert_eq!(total_weight(&by_kruskal), total_weight(&by_prim));
+        }
+    }
+}
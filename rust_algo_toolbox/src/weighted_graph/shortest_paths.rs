@@ -0,0 +1,425 @@
+//! Single-source shortest paths
+
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    ops::Add,
+};
+
+use num_traits::Zero;
+
+use crate::pqueue::indexed_heap::IndexedMinHeap;
+
+use super::{Graph, VertexID};
+
+/// Weight which can be summed along a path
+pub trait PathWeight: Copy + PartialOrd + Zero + Add<Output = Self> {}
+
+impl<Weight> PathWeight for Weight where Weight: Copy + PartialOrd + Zero + Add<Output = Self> {}
+
+/// Distances from a single source vertex to all reachable vertices
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShortestPaths<Weight> {
+    source: VertexID,
+    distances: HashMap<VertexID, Weight>,
+    predecessors: HashMap<VertexID, VertexID>,
+}
+
+impl<Weight: Copy> ShortestPaths<Weight> {
+    /// id of the vertex all paths start at
+    pub fn source(&self) -> VertexID {
+        self.source
+    }
+
+    /// length of the shortest path to the vertex or None if it's unreachable
+    pub fn distance_to(&self, id: VertexID) -> Option<Weight> {
+        self.distances.get(&id).copied()
+    }
+
+    /// previous vertex on the shortest path to the vertex
+    pub fn predecessor_of(&self, id: VertexID) -> Option<VertexID> {
+        self.predecessors.get(&id).copied()
+    }
+
+    /// distances to all reachable vertices
+    pub fn distances(&self) -> &HashMap<VertexID, Weight> {
+        &self.distances
+    }
+
+    /// previous vertices on the shortest paths to all reachable vertices (except the source)
+    pub fn predecessors(&self) -> &HashMap<VertexID, VertexID> {
+        &self.predecessors
+    }
+
+    /// vertices of the shortest path from the source to the vertex (both ends included)
+    pub fn path_to(&self, id: VertexID) -> Option<Vec<VertexID>> {
+        if !self.distances.contains_key(&id) {
+            return None;
+        }
+
+        let mut path = vec![id];
+        let mut current = id;
+        while let Some(&previous) = self.predecessors.get(&current) {
+            path.push(previous);
+            current = previous;
+        }
+        path.reverse();
+
+        Some(path)
+    }
+}
+
+/// Error returned when a negative cycle is reachable from the source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NegativeCycle {
+    /// vertices of the cycle in traversal order (the first vertex isn't repeated at the end)
+    pub cycle: Vec<VertexID>,
+}
+
+impl Display for NegativeCycle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "negative cycle through vertices {:?}", self.cycle)
+    }
+}
+
+impl std::error::Error for NegativeCycle {}
+
+/// Heap entry ordered by its score.
+/// Incomparable scores (e.g. NaN) are treated as equal
+struct Scored<Weight>(Weight, VertexID);
+
+impl<Weight: PartialOrd> PartialEq for Scored<Weight> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<Weight: PartialOrd> Eq for Scored<Weight> {}
+
+impl<Weight: PartialOrd> PartialOrd for Scored<Weight> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<Weight: PartialOrd> Ord for Scored<Weight> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0
+            .partial_cmp(&other.0)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| self.1.cmp(&other.1))
+    }
+}
+
+/// Compute shortest paths from `source` using Dijkstra's algorithm.
+/// All weights are expected to be non-negative
+pub fn dijkstra<G>(graph: &G, source: VertexID) -> ShortestPaths<G::Weight>
+where
+    G: Graph,
+    G::Weight: PathWeight,
+{
+    assert!(graph.contains(source), "source vertex doesn't exist");
+
+    let mut distances = HashMap::new();
+    let mut predecessors = HashMap::new();
+    let mut finished = HashSet::new();
+
+    distances.insert(source, G::Weight::zero());
+    let mut queue = IndexedMinHeap::<_>::new();
+    let mut handles = HashMap::new();
+    handles.insert(source, queue.push(Scored(G::Weight::zero(), source)));
+
+    while let Some((_, Scored(distance, id))) = queue.pop() {
+        finished.insert(id);
+
+        for edge in graph.out_edges(id) {
+            if finished.contains(&edge.to) {
+                continue;
+            }
+
+            let new_distance = distance + edge.weight;
+            let improved = distances
+                .get(&edge.to)
+                .is_none_or(|&known| new_distance < known);
+
+            if improved {
+                distances.insert(edge.to, new_distance);
+                predecessors.insert(edge.to, id);
+
+                // every vertex is queued once and moved up when a shorter path is found
+                let entry = Scored(new_distance, edge.to);
+                match handles.get(&edge.to) {
+                    Some(&handle) => {
+                        queue.change_priority(handle, entry);
+                    }
+                    None => {
+                        handles.insert(edge.to, queue.push(entry));
+                    }
+                }
+            }
+        }
+    }
+
+    ShortestPaths {
+        source,
+        distances,
+        predecessors,
+    }
+}
+
+/// Compute shortest paths from `source` using Bellman-Ford algorithm.
+/// Negative weights are allowed, but the search fails if a negative cycle is reachable from `source`
+pub fn bellman_ford<G>(
+    graph: &G,
+    source: VertexID,
+) -> Result<ShortestPaths<G::Weight>, NegativeCycle>
+where
+    G: Graph,
+    G::Weight: PathWeight,
+{
+    assert!(graph.contains(source), "source vertex doesn't exist");
+
+    let mut distances = HashMap::new();
+    distances.insert(source, G::Weight::zero());
+
+    let (distances, predecessors) = relax_until_stable(graph, distances)?;
+
+    Ok(ShortestPaths {
+        source,
+        distances,
+        predecessors,
+    })
+}
+
+type DistancesAndPredecessors<Weight> = (HashMap<VertexID, Weight>, HashMap<VertexID, VertexID>);
+
+/// Run Bellman-Ford relaxation rounds starting from given tentative distances.
+/// Returns final distances and predecessors or a negative cycle reachable from any of the starting vertices
+pub(super) fn relax_until_stable<G>(
+    graph: &G,
+    mut distances: HashMap<VertexID, G::Weight>,
+) -> Result<DistancesAndPredecessors<G::Weight>, NegativeCycle>
+where
+    G: Graph,
+    G::Weight: PathWeight,
+{
+    let mut predecessors = HashMap::new();
+    let vertex_ids: Vec<VertexID> = graph.vertex_ids().collect();
+
+    // relax every edge once more than needed, so that the last round detects negative cycles
+    let mut last_relaxed = None;
+    for _ in 0..vertex_ids.len() {
+        last_relaxed = None;
+
+        for &id in &vertex_ids {
+            let Some(&distance) = distances.get(&id) else {
+                continue;
+            };
+
+            for edge in graph.out_edges(id) {
+                let new_distance = distance + edge.weight;
+                let improved = distances
+                    .get(&edge.to)
+                    .is_none_or(|&known| new_distance < known);
+
+                if improved {
+                    distances.insert(edge.to, new_distance);
+                    predecessors.insert(edge.to, id);
+                    last_relaxed = Some(edge.to);
+                }
+            }
+        }
+
+        if last_relaxed.is_none() {
+            break;
+        }
+    }
+
+    let Some(mut id) = last_relaxed else {
+        return Ok((distances, predecessors));
+    };
+
+    // going back `n` times guarantees that we end up on the cycle itself
+    for _ in 0..vertex_ids.len() {
+        id = predecessors[&id];
+    }
+
+    let mut cycle = vec![id];
+    let mut current = predecessors[&id];
+    while current != id {
+        cycle.push(current);
+        current = predecessors[&current];
+    }
+    cycle.reverse();
+
+    Err(NegativeCycle { cycle })
+}
+
+/// Compute shortest paths from `source`.
+/// Dijkstra's algorithm is used if all weights are non-negative, Bellman-Ford otherwise
+pub fn shortest_paths_from<G>(
+    graph: &G,
+    source: VertexID,
+) -> Result<ShortestPaths<G::Weight>, NegativeCycle>
+where
+    G: Graph,
+    G::Weight: PathWeight,
+{
+    let zero = G::Weight::zero();
+    if graph.edges().all(|edge| edge.weight >= zero) {
+        Ok(dijkstra(graph, source))
+    } else {
+        bellman_ford(graph, source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::{collection::vec, prop_assert_eq, proptest};
+
+    use super::*;
+    use crate::weighted_graph::{DirectedWeightedGraph, WeightedGraph};
+
+    fn directed_graph(n: usize, edges: &[(usize, usize, i32)]) -> DirectedWeightedGraph<()> {
+        let mut graph = DirectedWeightedGraph::new();
+        for id in 0..n {
+            graph.insert(id, ());
+        }
+        for &(from, to, weight) in edges {
+            graph.connect(from, to, weight);
+        }
+
+        graph
+    }
+
+    #[test]
+    fn dijkstra_finds_shortest_paths() {
+        let mut graph = WeightedGraph::new();
+        for id in 0..5 {
+            graph.insert(id, ());
+        }
+        graph.connect(0, 1, 4);
+        graph.connect(0, 2, 1);
+        graph.connect(2, 1, 2);
+        graph.connect(1, 3, 1);
+        graph.connect(2, 3, 5);
+
+        let paths = dijkstra(&graph, 0);
+        assert_eq!(paths.distance_to(0), Some(0));
+        assert_eq!(paths.distance_to(1), Some(3));
+        assert_eq!(paths.distance_to(2), Some(1));
+        assert_eq!(paths.distance_to(3), Some(4));
+        assert_eq!(paths.distance_to(4), None);
+        assert_eq!(paths.path_to(3), Some(vec![0, 2, 1, 3]));
+        assert_eq!(paths.path_to(0), Some(vec![0]));
+        assert_eq!(paths.path_to(4), None);
+    }
+
+    #[test]
+    fn dijkstra_works_with_floats() {
+        let mut graph = DirectedWeightedGraph::new();
+        for id in 0..3 {
+            graph.insert(id, ());
+        }
+        graph.connect(0, 1, 0.5);
+        graph.connect(1, 2, 0.25);
+        graph.connect(0, 2, 1.0);
+
+        let paths = dijkstra(&graph, 0);
+        assert_eq!(paths.distance_to(2), Some(0.75));
+        assert_eq!(paths.path_to(2), Some(vec![0, 1, 2]));
+    }
+
+    #[test]
+    fn bellman_ford_handles_negative_weights() {
+        let graph = directed_graph(4, &[(0, 1, 4), (0, 2, 5), (2, 1, -3), (1, 3, 2)]);
+
+        let paths = graph.shortest_paths_from(0).unwrap();
+        assert_eq!(paths.distance_to(1), Some(2));
+        assert_eq!(paths.distance_to(3), Some(4));
+        assert_eq!(paths.path_to(3), Some(vec![0, 2, 1, 3]));
+    }
+
+    #[test]
+    fn bellman_ford_reports_negative_cycle() {
+        let graph = directed_graph(
+            5,
+            &[(0, 1, 1), (1, 2, 1), (2, 3, -1), (3, 1, -1), (3, 4, 1)],
+        );
+
+        let cycle = bellman_ford(&graph, 0).unwrap_err().cycle;
+        assert_eq!(cycle.len(), 3);
+        let start = cycle.iter().position(|&id| id == 1).unwrap();
+        let rotated: Vec<_> = cycle[start..]
+            .iter()
+            .chain(&cycle[..start])
+            .copied()
+            .collect();
+        assert_eq!(rotated, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn unreachable_negative_cycle_is_ignored() {
+        let graph = directed_graph(4, &[(0, 1, 1), (2, 3, -1), (3, 2, -1)]);
+
+        let paths = bellman_ford(&graph, 0).unwrap();
+        assert_eq!(paths.distance_to(1), Some(1));
+        assert_eq!(paths.distance_to(2), None);
+    }
+
+    proptest! {
+        #[test]
+        fn dijkstra_matches_bellman_ford(
+            edges in vec((0..20usize, 0..20usize, 0..100i32), 0..80),
+            source in 0..20usize,
+        ) {
+            let graph = directed_graph(20, &edges);
+
+            let expected = bellman_ford(&graph, source).unwrap();
+            let actual = dijkstra(&graph, source);
+
+            prop_assert_eq!(actual.distances(), expected.distances());
+            for (&id, &distance) in actual.distances() {
+                let path = actual.path_to(id).unwrap();
+                let path_len: i32 = path
+                    .windows(2)
+                    .map(|pair| graph.out_edges(pair[0]).find(|e| e.to == pair[1]).unwrap().weight)
+                    .sum();
+                prop_assert_eq!(path_len, distance);
+            }
+        }
+
+        #[test]
+        fn bellman_ford_on_dag_matches_dynamic_programming(
+            edges in vec((0..15usize, 0..15usize, -50..50i32), 0..60),
+        ) {
+            // edges always go from the smaller id to the bigger one, so there are no cycles
+            let edges: Vec<_> = edges
+                .into_iter()
+                .filter(|(from, to, _)| from != to)
+                .map(|(from, to, weight)| (from.min(to), from.max(to), weight))
+                .collect();
+            let graph = directed_graph(15, &edges);
+
+            let mut expected: Vec<Option<i32>> = vec![None; 15];
+            expected[0] = Some(0);
+            for id in 0..15 {
+                let Some(distance) = expected[id] else {
+                    continue;
+                };
+                for edge in graph.out_edges(id) {
+                    let new_distance = distance + edge.weight;
+                    if expected[edge.to].is_none_or(|known| new_distance < known) {
+                        expected[edge.to] = Some(new_distance);
+                    }
+                }
+            }
+
+            let paths = bellman_ford(&graph, 0).unwrap();
+            for (id, expected) in expected.into_iter().enumerate() {
+                prop_assert_eq!(paths.distance_to(id), expected);
+            }
+        }
+    }
+}
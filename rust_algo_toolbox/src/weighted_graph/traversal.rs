@@ -0,0 +1,319 @@
+//! Graph traversals and connectivity
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt::Display,
+};
+
+use crate::union_find::{Cluster, SparseUF, UnionFind};
+
+use super::{Graph, VertexID};
+
+/// Lazy breadth-first traversal yielding vertices in order of discovery
+pub struct Bfs<'a, G> {
+    graph: &'a G,
+    queue: VecDeque<VertexID>,
+    visited: HashSet<VertexID>,
+}
+
+impl<'a, G: Graph> Bfs<'a, G> {
+    /// start a traversal at a given vertex
+    pub fn new(graph: &'a G, start: VertexID) -> Self {
+        assert!(graph.contains(start), "start vertex doesn't exist");
+
+        Bfs {
+            graph,
+            queue: VecDeque::from([start]),
+            visited: HashSet::from([start]),
+        }
+    }
+}
+
+impl<G: Graph> Iterator for Bfs<'_, G> {
+    type Item = VertexID;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let id = self.queue.pop_front()?;
+
+        for edge in self.graph.out_edges(id) {
+            if self.visited.insert(edge.to) {
+                self.queue.push_back(edge.to);
+            }
+        }
+
+        Some(id)
+    }
+}
+
+/// Event emitted by a depth-first traversal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DfsEvent {
+    /// vertex is visited for the first time (pre-order)
+    Discover(VertexID),
+    /// all vertices reachable from the vertex are visited (post-order)
+    Finish(VertexID),
+}
+
+/// Lazy depth-first traversal yielding pre-order and post-order events
+pub struct Dfs<'a, G> {
+    graph: &'a G,
+    start: Option<VertexID>,
+    stack: Vec<(VertexID, std::vec::IntoIter<VertexID>)>,
+    visited: HashSet<VertexID>,
+}
+
+impl<'a, G: Graph> Dfs<'a, G> {
+    /// start a traversal at a given vertex
+    pub fn new(graph: &'a G, start: VertexID) -> Self {
+        assert!(graph.contains(start), "start vertex doesn't exist");
+
+        Dfs {
+            graph,
+            start: Some(start),
+            stack: vec![],
+            visited: HashSet::new(),
+        }
+    }
+
+    /// iterator over vertices in pre-order
+    pub fn preorder(self) -> impl Iterator<Item = VertexID> + 'a {
+        self.filter_map(|event| match event {
+            DfsEvent::Discover(id) => Some(id),
+            DfsEvent::Finish(_) => None,
+        })
+    }
+
+    /// iterator over vertices in post-order
+    pub fn postorder(self) -> impl Iterator<Item = VertexID> + 'a {
+        self.filter_map(|event| match event {
+            DfsEvent::Discover(_) => None,
+            DfsEvent::Finish(id) => Some(id),
+        })
+    }
+
+    fn discover(&mut self, id: VertexID) -> DfsEvent {
+        self.visited.insert(id);
+
+        let neighbours: Vec<_> = self.graph.out_edges(id).map(|edge| edge.to).collect();
+        self.stack.push((id, neighbours.into_iter()));
+
+        DfsEvent::Discover(id)
+    }
+}
+
+impl<G: Graph> Iterator for Dfs<'_, G> {
+    type Item = DfsEvent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(start) = self.start.take() {
+            return Some(self.discover(start));
+        }
+
+        loop {
+            let (id, neighbours) = self.stack.last_mut()?;
+
+            match neighbours.next() {
+                Some(next) if !self.visited.contains(&next) => return Some(self.discover(next)),
+                Some(_) => continue,
+                None => {
+                    let id = *id;
+                    self.stack.pop();
+                    return Some(DfsEvent::Finish(id));
+                }
+            }
+        }
+    }
+}
+
+/// Error returned when a directed graph has a cycle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle {
+    /// vertices of the cycle in traversal order (the first vertex isn't repeated at the end)
+    pub cycle: Vec<VertexID>,
+}
+
+impl Display for Cycle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "cycle through vertices {:?}", self.cycle)
+    }
+}
+
+impl std::error::Error for Cycle {}
+
+/// Split the graph into connected components.
+/// Edge directions are ignored, so weakly connected components are returned for directed graphs
+pub fn connected_components<G: Graph>(graph: &G) -> Vec<Cluster> {
+    let mut dsu = SparseUF::new();
+
+    for id in graph.vertex_ids() {
+        dsu.insert(id);
+    }
+
+    for edge in graph.edges() {
+        dsu.join(edge.from, edge.to);
+    }
+
+    dsu.clusters()
+}
+
+/// Order vertices so that every edge goes from an earlier vertex to a later one.
+/// Fails with one of the cycles if there is no such order.
+/// Every undirected edge is a cycle of two vertices, so it's meaningful for directed graphs only
+pub fn topological_sort<G: Graph>(graph: &G) -> Result<Vec<VertexID>, Cycle> {
+    let mut roots: Vec<_> = graph.vertex_ids().collect();
+    roots.sort_unstable();
+
+    let mut finished = HashSet::new();
+    let mut order = Vec::with_capacity(roots.len());
+
+    for root in roots {
+        if finished.contains(&root) {
+            continue;
+        }
+
+        // current path from the root and positions of its vertices on it
+        let mut stack: Vec<(VertexID, std::vec::IntoIter<VertexID>)> = vec![];
+        let mut positions = HashMap::new();
+
+        let neighbours: Vec<_> = graph.out_edges(root).map(|edge| edge.to).collect();
+        positions.insert(root, 0);
+        stack.push((root, neighbours.into_iter()));
+
+        while let Some((id, neighbours)) = stack.last_mut() {
+            let Some(next) = neighbours.next() else {
+                let id = *id;
+                stack.pop();
+                positions.remove(&id);
+                finished.insert(id);
+                order.push(id);
+                continue;
+            };
+
+            if let Some(&position) = positions.get(&next) {
+                let cycle = stack[position..].iter().map(|(id, _)| *id).collect();
+                return Err(Cycle { cycle });
+            }
+
+            if finished.contains(&next) {
+                continue;
+            }
+
+            let neighbours: Vec<_> = graph.out_edges(next).map(|edge| edge.to).collect();
+            positions.insert(next, stack.len());
+            stack.push((next, neighbours.into_iter()));
+        }
+    }
+
+    order.reverse();
+    Ok(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weighted_graph::{DirectedWeightedGraph, WeightedGraph};
+
+    #[rstest::fixture]
+    fn path_graph() -> WeightedGraph<()> {
+        // 0 - 1 - 2 - 3   4
+        let mut graph = WeightedGraph::new();
+        for id in 0..5 {
+            graph.insert(id, ());
+        }
+        graph.connect(0, 1, 1);
+        graph.connect(1, 2, 1);
+        graph.connect(2, 3, 1);
+
+        graph
+    }
+
+    #[rstest::fixture]
+    fn dag() -> DirectedWeightedGraph<()> {
+        // 0 -> 1 -> 3
+        //  \-> 2 -/
+        let mut graph = DirectedWeightedGraph::new();
+        for id in 0..4 {
+            graph.insert(id, ());
+        }
+        graph.connect(0, 1, 1);
+        graph.connect(0, 2, 1);
+        graph.connect(1, 3, 1);
+        graph.connect(2, 3, 1);
+
+        graph
+    }
+
+    #[rstest::rstest]
+    fn bfs_visits_by_distance(path_graph: WeightedGraph<()>) {
+        let order: Vec<_> = path_graph.bfs(1).collect();
+        assert_eq!(order.len(), 4);
+        assert_eq!(order[0], 1);
+        assert!(order[1..3].contains(&0));
+        assert!(order[1..3].contains(&2));
+        assert_eq!(order[3], 3);
+    }
+
+    #[rstest::rstest]
+    fn dfs_emits_balanced_events(path_graph: WeightedGraph<()>) {
+        let events: Vec<_> = path_graph.dfs(0).collect();
+        assert_eq!(
+            events,
+            vec![
+                DfsEvent::Discover(0),
+                DfsEvent::Discover(1),
+                DfsEvent::Discover(2),
+                DfsEvent::Discover(3),
+                DfsEvent::Finish(3),
+                DfsEvent::Finish(2),
+                DfsEvent::Finish(1),
+                DfsEvent::Finish(0),
+            ]
+        );
+    }
+
+    #[rstest::rstest]
+    fn dfs_respects_directions(dag: DirectedWeightedGraph<()>) {
+        let preorder: Vec<_> = dag.dfs(1).preorder().collect();
+        assert_eq!(preorder, vec![1, 3]);
+
+        let postorder: Vec<_> = dag.dfs(0).postorder().collect();
+        assert_eq!(postorder.len(), 4);
+        assert_eq!(postorder[0], 3);
+        assert_eq!(postorder[3], 0);
+    }
+
+    #[rstest::rstest]
+    fn connected_components_are_found(path_graph: WeightedGraph<()>) {
+        let mut components: Vec<_> = path_graph
+            .connected_components()
+            .into_iter()
+            .map(|cluster| cluster.nodes.len())
+            .collect();
+        components.sort_unstable();
+
+        assert_eq!(components, vec![1, 4]);
+    }
+
+    #[rstest::rstest]
+    fn topological_sort_orders_edges(dag: DirectedWeightedGraph<()>) {
+        let order = dag.topological_sort().unwrap();
+        assert_eq!(order.len(), 4);
+
+        let position = |id| order.iter().position(|&v| v == id).unwrap();
+        for edge in dag.edges() {
+            assert!(position(edge.from) < position(edge.to));
+        }
+    }
+
+    #[rstest::rstest]
+    fn topological_sort_reports_cycle(mut dag: DirectedWeightedGraph<()>) {
+        dag.insert(4, ());
+        dag.connect(3, 4, 1);
+        dag.connect(4, 1, 1);
+
+        let mut cycle = dag.topological_sort().unwrap_err().cycle;
+        let start = cycle.iter().position(|&id| id == 1).unwrap();
+        cycle.rotate_left(start);
+        assert_eq!(cycle, vec![1, 3, 4]);
+    }
+}